@@ -61,6 +61,38 @@ fn route(c: &mut Criterion) {
     });
 }
 
+fn matrix(c: &mut Criterion) {
+    let config = Config::from_file(ANDORRA_CONFIG).unwrap();
+    let mut actor = Actor::new(&config).unwrap();
+
+    c.bench_function("matrix", |b| {
+        let request = proto::Api {
+            options: Some(proto::Options {
+                costing_type: proto::costing::Type::Auto as i32,
+                sources: vec![
+                    proto::Location {
+                        ll: Some(ANDORRA_TEST_LOC_1.into()),
+                        ..Default::default()
+                    },
+                ],
+                targets: vec![
+                    proto::Location {
+                        ll: Some(ANDORRA_TEST_LOC_2.into()),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        b.iter(|| {
+            let response = actor.matrix(black_box(&request)).unwrap();
+            black_box(response)
+        });
+    });
+}
+
 fn trace_attributes(c: &mut Criterion) {
     let config = Config::from_file(ANDORRA_CONFIG).unwrap();
     let mut actor = Actor::new(&config).unwrap();
@@ -103,6 +135,58 @@ fn trace_attributes(c: &mut Criterion) {
     });
 }
 
+fn isochrone(c: &mut Criterion) {
+    let config = Config::from_file(ANDORRA_CONFIG).unwrap();
+    let mut actor = Actor::new(&config).unwrap();
+
+    c.bench_function("isochrone json", |b| {
+        let request = proto::Api {
+            options: Some(proto::Options {
+                costing_type: proto::costing::Type::Pedestrian as i32,
+                locations: vec![proto::Location {
+                    ll: Some(ANDORRA_TEST_LOC_1.into()),
+                    ..Default::default()
+                }],
+                contours: vec![proto::Contour {
+                    has_time: Some(proto::contour::HasTime::Time(10.0)),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        b.iter(|| {
+            let response = actor.isochrone(black_box(&request)).unwrap();
+            black_box(response)
+        });
+    });
+
+    c.bench_function("isochrone pbf", |b| {
+        let request = proto::Api {
+            options: Some(proto::Options {
+                costing_type: proto::costing::Type::Pedestrian as i32,
+                locations: vec![proto::Location {
+                    ll: Some(ANDORRA_TEST_LOC_1.into()),
+                    ..Default::default()
+                }],
+                contours: vec![proto::Contour {
+                    has_time: Some(proto::contour::HasTime::Time(10.0)),
+                    ..Default::default()
+                }],
+                format: proto::options::Format::Pbf as i32,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        b.iter(|| {
+            let response = actor.isochrone(black_box(&request)).unwrap();
+            black_box(response)
+        });
+    });
+}
+
 fn locate(c: &mut Criterion) {
     let config = Config::from_file(ANDORRA_CONFIG).unwrap();
     let mut actor = Actor::new(&config).unwrap();
@@ -162,5 +246,5 @@ fn status(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, route, trace_attributes, locate, status);
+criterion_group!(benches, route, matrix, isochrone, trace_attributes, locate, status);
 criterion_main!(benches);
@@ -0,0 +1,37 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+use libvalhalla::{Config, GraphLevel, GraphReader, LatLon};
+
+const ANDORRA_CONFIG: &str = "../tests/andorra/config.json";
+const ANDORRA_MIN: LatLon = LatLon(42.4288, 1.4135);
+const ANDORRA_MAX: LatLon = LatLon(42.6559, 1.7862);
+
+fn traffic_flows_in_bbox(c: &mut Criterion) {
+    let config = Config::from_file(ANDORRA_CONFIG).unwrap();
+    let graph_reader = GraphReader::new(&config).unwrap();
+    let levels = [GraphLevel::Highway, GraphLevel::Arterial, GraphLevel::Local];
+
+    c.bench_function("traffic_flows_in_bbox (parallel)", |b| {
+        b.iter(|| {
+            let flows: Vec<_> = graph_reader
+                .traffic_flows_in_bbox(black_box(ANDORRA_MIN), black_box(ANDORRA_MAX), &levels)
+                .collect();
+            black_box(flows)
+        });
+    });
+
+    c.bench_function("tiles_in_bbox + get_tile_traffic_flows (serial)", |b| {
+        b.iter(|| {
+            let flows: Vec<_> = levels
+                .iter()
+                .flat_map(|&level| graph_reader.tiles_in_bbox(black_box(ANDORRA_MIN), black_box(ANDORRA_MAX), level))
+                .map(|id| (id, graph_reader.get_tile_traffic_flows(id)))
+                .collect();
+            black_box(flows)
+        });
+    });
+}
+
+criterion_group!(benches, traffic_flows_in_bbox);
+criterion_main!(benches);
@@ -1,6 +1,19 @@
 use std::{os::unix::ffi::OsStrExt, path::PathBuf};
 
 pub use ffi::GraphLevel;
+pub use ffi::TrafficUpdate;
+
+/// Errors that can occur while loading a Valhalla configuration or building a [`GraphReader`]
+/// from it.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("config file not found: {0}")]
+    ConfigNotFound(PathBuf),
+    #[error("failed to build tileset from {path}: {reason}")]
+    TileSetBuild { path: PathBuf, reason: String },
+    #[error("failed to write traffic for tile {tile}: {reason}")]
+    TrafficWrite { tile: u64, reason: String },
+}
 
 #[cxx::bridge]
 mod ffi {
@@ -10,12 +23,33 @@ mod ffi {
         Local,
     }
 
+    /// One edge's worth of live-traffic data to write into a tile's `TrafficSpeed` record.
+    struct TrafficUpdate {
+        /// Index of the edge within the tile, matching `DirectedEdge`'s position in the tile.
+        edge_index: u32,
+        /// Current average speed, in km/h.
+        speed_kmh: u8,
+        /// Congestion level in `0..=255`, where `0` is free flow and `255` is gridlock.
+        congestion: u8,
+        /// Historical-speed breakpoints `TrafficSpeed` uses to blend live and predicted speeds.
+        breakpoint1: u8,
+        breakpoint2: u8,
+    }
+
     unsafe extern "C++" {
         include!("libvalhalla/src/libvalhalla.hpp");
 
         type TrafficEdge;
         fn shape(self: &TrafficEdge) -> &CxxString;
         fn jam_factor(self: &TrafficEdge) -> f32;
+        /// `GraphId` of the directed edge this flow was computed from, as its raw `u64` value.
+        fn graph_id(self: &TrafficEdge) -> u64;
+        fn road_class(self: &TrafficEdge) -> GraphLevel;
+        /// Speed limit, or the default speed used to compute the jam factor if no limit is posted, in km/h.
+        fn speed_kmh(self: &TrafficEdge) -> u8;
+        fn length_m(self: &TrafficEdge) -> f32;
+        /// Whether this flow covers the edge in its forward (stored) direction or the reverse one.
+        fn forward(self: &TrafficEdge) -> bool;
 
         type GraphLevel;
 
@@ -30,6 +64,11 @@ mod ffi {
             level: GraphLevel,
         ) -> Vec<u64>;
         fn get_tile_traffic(self: &TileSet, id: u64) -> UniquePtr<CxxVector<TrafficEdge>>;
+
+        /// Writes `updates` into the live-traffic slots of tile `id` in the memory-mapped
+        /// `traffic.tar`. The write is atomic per tile: the shim stages the packed `TrafficSpeed`
+        /// records and swaps them in under the tile's lock rather than mutating slots in place.
+        fn set_tile_traffic(self: &TileSet, id: u64, updates: &[TrafficUpdate]) -> Result<()>;
     }
 }
 
@@ -37,10 +76,94 @@ mod ffi {
 unsafe impl Send for ffi::TileSet {}
 unsafe impl Sync for ffi::TileSet {}
 
+/// Path to a Valhalla configuration file (as produced by `valhalla_build_config`), validated to
+/// exist on disk so construction failures surface at config-load time rather than deep inside FFI.
+#[derive(Clone, Debug)]
+pub struct Config(PathBuf);
+
+impl Config {
+    /// Reads configuration from the given Valhalla configuration file.
+    pub fn from_file(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        if !path.exists() {
+            return Err(Error::ConfigNotFound(path));
+        }
+        Ok(Self(path))
+    }
+}
+
 /// Coordinate in (lat, lon) format
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct LatLon(pub f32, pub f32);
 
+impl LatLon {
+    /// Decodes a Google-style polyline (as returned in [`TrafficEdge::shape`]) into points at the
+    /// given `precision` (Valhalla's shape output uses 6).
+    pub fn decode_polyline(encoded: &str, precision: u32) -> Vec<LatLon> {
+        let factor = 10f32.powi(precision as i32);
+        let mut points = Vec::new();
+        let mut lat = 0i64;
+        let mut lon = 0i64;
+        let mut chars = encoded.chars().peekable();
+
+        while chars.peek().is_some() {
+            let (Some(dlat), Some(dlon)) = (decode_value(&mut chars), decode_value(&mut chars)) else {
+                break;
+            };
+            lat += dlat;
+            lon += dlon;
+            points.push(LatLon(lat as f32 / factor, lon as f32 / factor));
+        }
+        points
+    }
+}
+
+/// Encodes points as a Google-style polyline at the given `precision` (Valhalla's shape output
+/// uses 6). The inverse of [`LatLon::decode_polyline`].
+pub fn encode_polyline(points: &[LatLon], precision: u32) -> String {
+    let factor = 10f32.powi(precision as i32);
+    let mut encoded = String::new();
+    let mut prev_lat = 0i64;
+    let mut prev_lon = 0i64;
+
+    for point in points {
+        let lat = (point.0 * factor).round() as i64;
+        let lon = (point.1 * factor).round() as i64;
+        encode_value(lat - prev_lat, &mut encoded);
+        encode_value(lon - prev_lon, &mut encoded);
+        prev_lat = lat;
+        prev_lon = lon;
+    }
+    encoded
+}
+
+fn encode_value(value: i64, out: &mut String) {
+    let mut value = value << 1;
+    if value < 0 {
+        value = !value;
+    }
+    while value >= 0x20 {
+        let chunk = (0x20 | (value & 0x1f)) as u8 + 63;
+        out.push(chunk as char);
+        value >>= 5;
+    }
+    out.push((value as u8 + 63) as char);
+}
+
+fn decode_value(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<i64> {
+    let mut shift = 0;
+    let mut result: i64 = 0;
+    loop {
+        let byte = chars.next()? as i64 - 63;
+        result |= (byte & 0x1f) << shift;
+        shift += 5;
+        if byte < 0x20 {
+            break;
+        }
+    }
+    Some(if result & 1 != 0 { !(result >> 1) } else { result >> 1 })
+}
+
 /// Road graph tile id
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct TileId(pub u64);
@@ -51,6 +174,24 @@ pub struct TrafficEdge {
     pub shape: String,
     /// Ration between live speed and speed limit (or default edge speed if speed limit is unavailable).
     pub jam_factor: f32,
+    /// Raw `u64` value of the directed edge's `GraphId`, so flows can be joined back to the graph.
+    pub graph_id: u64,
+    /// Functional road class / hierarchy level of the edge.
+    pub road_class: GraphLevel,
+    /// Speed limit, or the default speed used to compute `jam_factor` if no limit is posted, in km/h.
+    pub speed_kmh: u8,
+    /// Length of the edge, in meters.
+    pub length_m: f32,
+    /// Whether this flow covers the edge in its forward (stored) direction or the reverse one.
+    pub forward: bool,
+}
+
+impl TrafficEdge {
+    /// Decodes [`Self::shape`] into points, as a convenience over calling
+    /// [`LatLon::decode_polyline`] directly.
+    pub fn decoded_shape(&self) -> Vec<LatLon> {
+        LatLon::decode_polyline(&self.shape, 6)
+    }
 }
 
 #[derive(Clone)]
@@ -59,11 +200,15 @@ pub struct GraphReader {
 }
 
 impl GraphReader {
-    pub fn new(config_file: PathBuf) -> Self {
-        cxx::let_cxx_string!(cxx_str = config_file.as_os_str().as_bytes());
-        Self {
-            tileset: ffi::new_tileset(&cxx_str).unwrap(),
-        }
+    /// Builds a `GraphReader` from the given configuration, failing instead of panicking if the
+    /// tile/traffic extracts it references are missing or corrupt.
+    pub fn new(config: &Config) -> Result<Self, Error> {
+        cxx::let_cxx_string!(cxx_str = config.0.as_os_str().as_bytes());
+        let tileset = ffi::new_tileset(&cxx_str).map_err(|err| Error::TileSetBuild {
+            path: config.0.clone(),
+            reason: err.what().to_string(),
+        })?;
+        Ok(Self { tileset })
     }
 
     pub fn tiles_in_bbox(&self, min: LatLon, max: LatLon, level: GraphLevel) -> Vec<TileId> {
@@ -81,7 +226,59 @@ impl GraphReader {
             .map(|flow| TrafficEdge {
                 shape: flow.shape().to_string(),
                 jam_factor: flow.jam_factor(),
+                graph_id: flow.graph_id(),
+                road_class: flow.road_class(),
+                speed_kmh: flow.speed_kmh(),
+                length_m: flow.length_m(),
+                forward: flow.forward(),
             })
             .collect()
     }
+
+    /// Gathers live traffic for every tile in `[min, max]` across all of `levels`, fanning the
+    /// per-tile `get_tile_traffic` calls out across a rayon thread pool since [`TileSet`] is
+    /// already `Send + Sync`.
+    ///
+    /// Results are ordered by ascending [`TileId`] regardless of which tile finishes fetching
+    /// first, so callers get a deterministic result for a given bbox/levels pair.
+    pub fn traffic_flows_in_bbox(
+        &self,
+        min: LatLon,
+        max: LatLon,
+        levels: &[GraphLevel],
+    ) -> impl Iterator<Item = (TileId, Vec<TrafficEdge>)> {
+        use rayon::prelude::*;
+
+        let mut tile_ids: Vec<TileId> = levels
+            .iter()
+            .flat_map(|&level| self.tiles_in_bbox(min, max, level))
+            .collect();
+        tile_ids.sort_by_key(|tile| tile.0);
+        tile_ids.dedup();
+
+        tile_ids
+            .into_par_iter()
+            .map(|id| (id, self.get_tile_traffic_flows(id)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Writes live-traffic `updates` into tile `id`'s memory-mapped `traffic.tar` record.
+    ///
+    /// The write is atomic per tile: the C++ shim stages the packed `TrafficSpeed` records and
+    /// swaps them in under the tile's lock, so concurrent readers never observe a partially
+    /// written tile.
+    ///
+    /// # Invariant
+    /// The `traffic.tar` must have been pre-allocated (via `valhalla_build_extract`) with an edge
+    /// count matching the tile's `tile_extract`; `updates[i].edge_index` beyond that count is
+    /// rejected by the shim rather than writing out of bounds.
+    pub fn set_tile_traffic(&self, id: TileId, updates: &[TrafficUpdate]) -> Result<(), Error> {
+        self.tileset
+            .set_tile_traffic(id.0, updates)
+            .map_err(|err| Error::TrafficWrite {
+                tile: id.0,
+                reason: err.what().to_string(),
+            })
+    }
 }
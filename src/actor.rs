@@ -81,6 +81,16 @@ pub enum Response {
     Other(Vec<u8>),
 }
 
+/// A deprecation or fallback notice Valhalla attaches to a response, e.g. when a request uses a
+/// deprecated costing such as `auto_shorter` or `hov`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    /// Machine-readable warning code, stable across Valhalla versions.
+    pub code: u32,
+    /// Human-readable description of the warning.
+    pub text: String,
+}
+
 impl From<ffi::Response> for Response {
     fn from(response: ffi::Response) -> Self {
         if response.format == Format::Pbf as i32 {
@@ -95,6 +105,67 @@ impl From<ffi::Response> for Response {
     }
 }
 
+/// Why [`Actor::route_with_fallback`] fell back to a different costing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FallbackReason {
+    /// The engine could not find a path using the preferred costing (e.g. no pedestrian-reachable
+    /// route between the given locations).
+    NoPathFound,
+    /// Any other error raised while computing the route, e.g. an invalid request.
+    EngineError,
+}
+
+/// Reports that [`Actor::route_with_fallback`] had to retry with a different costing than the one
+/// originally requested.
+#[derive(Debug, Clone, Copy)]
+pub struct FallbackInfo {
+    /// Costing that actually produced the returned [`Response`].
+    pub costing_used: proto::costing::Type,
+    /// Why the primary costing was abandoned.
+    pub reason: FallbackReason,
+}
+
+impl Response {
+    /// Returns deprecation/fallback warnings Valhalla attached to this response, regardless of
+    /// which format was requested.
+    ///
+    /// For [`Response::Pbf`] these come straight from the decoded [`proto::Api`]. For
+    /// [`Response::Json`] the top-level `"warnings"` array is lazily parsed out of the raw JSON
+    /// blob, so callers who don't care about warnings pay nothing for this.
+    pub fn warnings(&self) -> Vec<Warning> {
+        match self {
+            Response::Pbf(api) => api
+                .warnings
+                .iter()
+                .map(|warning| Warning {
+                    code: warning.code,
+                    text: warning.text.clone(),
+                })
+                .collect(),
+            Response::Json(json) => {
+                let Ok(value) = serde_json::from_str::<serde_json::Value>(json) else {
+                    return Vec::new();
+                };
+                let Some(warnings) = value.get("warnings").and_then(|w| w.as_array()) else {
+                    return Vec::new();
+                };
+                warnings
+                    .iter()
+                    .map(|warning| Warning {
+                        code: warning.get("code").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
+                        text: warning
+                            .get("text")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or_default()
+                            .to_string(),
+                    })
+                    .collect()
+            }
+            Response::Other(_) => Vec::new(),
+        }
+    }
+}
+
 /// High-level interface to interact with [Valhalla's API](https://valhalla.github.io/valhalla/api/).
 /// On contrary to the Valhalla REST and C++ APIs, this interface is designed to be used with [`proto::Options`] only,
 /// to avoid unnecessary conversions and to provide a strongly typed interface.
@@ -152,6 +223,65 @@ impl Actor {
         self.act(ffi::Actor::route, request)
     }
 
+    /// Calculates a route, retrying with each of `fallbacks` in order if the preferred
+    /// `request.costing_type` fails, e.g. falling back from `Auto` to `Pedestrian` when no
+    /// drivable route exists. Returns `(response, None)` if the primary costing succeeded, or
+    /// `(response, Some(info))` naming which fallback was used and why.
+    ///
+    /// # Example
+    /// ```
+    /// # fn call_route_with_fallback(mut actor: valhalla::Actor) {
+    /// use valhalla::proto;
+    ///
+    /// let request = proto::Options {
+    ///     costing_type: proto::costing::Type::Auto as i32,
+    ///     locations: vec![
+    ///         proto::Location {
+    ///             ll: valhalla::LatLon(55.6086, 13.0005).into(),
+    ///             ..Default::default()
+    ///         },
+    ///         proto::Location {
+    ///             ll: valhalla::LatLon(55.5944, 13.0002).into(),
+    ///             ..Default::default()
+    ///         },
+    ///     ],
+    ///     ..Default::default()
+    /// };
+    /// let (response, fallback) = actor
+    ///     .route_with_fallback(&request, &[proto::costing::Type::Pedestrian])
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn route_with_fallback(
+        &mut self,
+        request: &proto::Options,
+        fallbacks: &[proto::costing::Type],
+    ) -> Result<(Response, Option<FallbackInfo>), Error> {
+        match self.route(request) {
+            Ok(response) => Ok((response, None)),
+            Err(err) => {
+                let reason = if err.0.contains("No path") {
+                    FallbackReason::NoPathFound
+                } else {
+                    FallbackReason::EngineError
+                };
+
+                let mut last_err = err;
+                for &costing_used in fallbacks {
+                    let mut request = request.clone();
+                    request.costing_type = costing_used as i32;
+                    match self.route(&request) {
+                        Ok(response) => {
+                            return Ok((response, Some(FallbackInfo { costing_used, reason })));
+                        }
+                        Err(err) => last_err = err,
+                    }
+                }
+                Err(last_err)
+            }
+        }
+    }
+
     /// Finds the nearest roads and intersections to input coordinates. Always returns a Valhalla JSON response.
     ///
     /// # Example
@@ -414,6 +544,39 @@ impl Actor {
         self.act(ffi::Actor::status, request)
     }
 
+    /// Dispatches a request to whichever endpoint matches its `action` field, mirroring
+    /// Valhalla's own `valhalla_service` dispatch table. Useful when the action isn't known ahead
+    /// of time, e.g. when forwarding arbitrary requests from an HTTP client.
+    ///
+    /// Returns [`Error`] for actions without a corresponding endpoint, such as `Height` (the
+    /// elevation service) or `NoAction`.
+    pub fn act_request(&mut self, request: &proto::Options) -> Result<Response, Error> {
+        match proto::options::Action::try_from(request.action) {
+            Ok(proto::options::Action::Route) => self.route(request),
+            Ok(proto::options::Action::Locate) => self.locate(request),
+            Ok(proto::options::Action::SourcesToTargets) => self.matrix(request),
+            Ok(proto::options::Action::OptimizedRoute) => self.optimized_route(request),
+            Ok(proto::options::Action::Isochrone) => self.isochrone(request),
+            Ok(proto::options::Action::TraceRoute) => self.trace_route(request),
+            Ok(proto::options::Action::TraceAttributes) => self.trace_attributes(request),
+            Ok(proto::options::Action::TransitAvailable) => self.transit_available(request),
+            Ok(proto::options::Action::Expansion) => self.expansion(request),
+            Ok(proto::options::Action::Centroid) => self.centroid(request),
+            Ok(proto::options::Action::Status) => self.status(request),
+            Ok(action) => Err(Error(format!("No endpoint for action {action:?}").into())),
+            Err(err) => Err(Error(err.to_string().into())),
+        }
+    }
+
+    /// Parses a Valhalla JSON request for the given action and dispatches it to the matching
+    /// endpoint, mirroring Valhalla's `valhalla_service` one-shot mode. This is not optimized for
+    /// performance and should be considered a convenience method; construct [`proto::Options`]
+    /// directly and call [`Actor::act_request`] for best performance.
+    pub fn one_shot(&mut self, json: &str, action: proto::options::Action) -> Result<Response, Error> {
+        let request = Self::parse_json_request(json, action)?;
+        self.act_request(&request)
+    }
+
     /// Generic helper function to process request encoding, calling the endpoint and handling cleanup.
     fn act<F>(&mut self, action_fn: F, request: &proto::Options) -> Result<Response, Error>
     where
@@ -0,0 +1,76 @@
+use std::{collections::HashSet, fmt};
+
+use axum::http::HeaderMap;
+
+/// Identity of the caller that authentication resolved, as passed down to route handlers.
+#[derive(Debug, Clone)]
+pub struct Principal {
+    /// Opaque name of the caller, e.g. the accepted API key or `"anonymous"` for the no-op auth.
+    pub name: String,
+}
+
+/// Error returned when a request fails authentication.
+#[derive(Debug, Clone)]
+pub struct AuthError(pub String);
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// Pluggable authentication strategy for the proxy endpoints.
+///
+/// Downstream users can implement this for their own scheme (JWT, HMAC-signed requests, etc.)
+/// without forking the server, since [`AppState`](crate::AppState) only holds a boxed trait object.
+pub trait ApiAuth: Send + Sync {
+    /// Authenticates a request from its headers, returning the resolved [`Principal`] on success.
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Principal, AuthError>;
+}
+
+/// No-op authentication that allows every request through. This is the default when no API keys
+/// are configured, matching the proxy's previous behavior.
+pub struct AllowAll;
+
+impl ApiAuth for AllowAll {
+    fn authenticate(&self, _headers: &HeaderMap) -> Result<Principal, AuthError> {
+        Ok(Principal {
+            name: "anonymous".to_string(),
+        })
+    }
+}
+
+/// Checks an `Authorization: Bearer <key>` header against a fixed set of accepted API keys.
+pub struct BearerApiKeyAuth {
+    keys: HashSet<String>,
+}
+
+impl BearerApiKeyAuth {
+    /// Creates a new checker accepting any of the given keys.
+    pub fn new(keys: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            keys: keys.into_iter().collect(),
+        }
+    }
+}
+
+impl ApiAuth for BearerApiKeyAuth {
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Principal, AuthError> {
+        let header = headers
+            .get(axum::http::header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| AuthError("Missing Authorization header".to_string()))?;
+
+        let key = header
+            .strip_prefix("Bearer ")
+            .ok_or_else(|| AuthError("Expected a Bearer token".to_string()))?;
+
+        if self.keys.contains(key) {
+            Ok(Principal { name: key.to_string() })
+        } else {
+            Err(AuthError("Unknown API key".to_string()))
+        }
+    }
+}
@@ -1,6 +1,7 @@
-use std::{os::unix::ffi::OsStrExt, path::Path};
+use std::{collections::HashMap, os::unix::ffi::OsStrExt, path::{Path, PathBuf}};
 
-use anyhow::Result;
+use anyhow::{Context, Result, ensure};
+use serde_json::{Value, json};
 
 #[cxx::bridge]
 pub(crate) mod ffi {
@@ -57,4 +58,122 @@ impl Config {
     pub(crate) fn inner(&self) -> &ffi::ptree {
         self.0.as_ref().unwrap()
     }
+
+    /// Starts building a configuration from typed setters instead of hand-assembled JSON. See
+    /// [`ConfigBuilder`].
+    /// ```rust
+    /// let config = valhalla::Config::builder()
+    ///     .tile_extract("path/to/tiles.tar")
+    ///     .and_then(|builder| builder.build());
+    /// ```
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+}
+
+/// Builds a [`Config`] from typed setters rather than hand-formatted JSON, validating that
+/// referenced paths exist up front so mistakes surface at config-build time rather than deep
+/// inside `GraphReader::new`.
+///
+/// Starting from [`Config::builder`] begins from an empty configuration; starting from
+/// [`ConfigBuilder::from_file`]/[`ConfigBuilder::from_json`] loads a full base configuration (e.g.
+/// one covering mjolnir, loki, thor, and service limits) and layers the typed setters as
+/// overrides on top of it at [`Self::build`] time.
+#[derive(Default)]
+pub struct ConfigBuilder {
+    base: Option<Value>,
+    tile_extract: Option<PathBuf>,
+    traffic_extract: Option<PathBuf>,
+    tile_dir: Option<PathBuf>,
+    costing_defaults: HashMap<String, Value>,
+}
+
+impl ConfigBuilder {
+    /// Loads a base configuration from file to layer overrides onto.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file {}", path.display()))?;
+        Ok(Self {
+            base: Some(serde_json::from_str(&text).with_context(|| format!("invalid JSON in config file {}", path.display()))?),
+            ..Default::default()
+        })
+    }
+
+    /// Loads a base configuration from a JSON string to layer overrides onto.
+    pub fn from_json(config: &str) -> Result<Self> {
+        Ok(Self {
+            base: Some(serde_json::from_str(config).context("invalid config JSON")?),
+            ..Default::default()
+        })
+    }
+
+    /// Sets `mjolnir.tile_extract`, failing if the path doesn't exist.
+    pub fn tile_extract(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        ensure!(path.exists(), "tile extract not found: {}", path.display());
+        self.tile_extract = Some(path.to_path_buf());
+        Ok(self)
+    }
+
+    /// Sets `mjolnir.traffic_extract`, failing if the path doesn't exist.
+    pub fn traffic_extract(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        ensure!(path.exists(), "traffic extract not found: {}", path.display());
+        self.traffic_extract = Some(path.to_path_buf());
+        Ok(self)
+    }
+
+    /// Sets `mjolnir.tile_dir`, failing if the path doesn't exist.
+    pub fn tile_dir(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        ensure!(path.exists(), "tile dir not found: {}", path.display());
+        self.tile_dir = Some(path.to_path_buf());
+        Ok(self)
+    }
+
+    /// Sets default costing options for the given costing name (e.g. `"auto"`, `"bicycle"`),
+    /// stored as `costing_options.<name>`, replacing any options the base configuration has for
+    /// that costing.
+    pub fn costing_default(mut self, costing: &str, options: Value) -> Self {
+        self.costing_defaults.insert(costing.to_string(), options);
+        self
+    }
+
+    /// Builds the final [`Config`], merging the typed overrides onto the base configuration (if
+    /// any) and serializing the result to the JSON Valhalla expects.
+    pub fn build(self) -> Result<Config> {
+        let mut root = self.base.unwrap_or_else(|| json!({}));
+        let root_map = root.as_object_mut().context("config root must be a JSON object")?;
+
+        if self.tile_extract.is_some() || self.traffic_extract.is_some() || self.tile_dir.is_some() {
+            let mjolnir = root_map
+                .entry("mjolnir")
+                .or_insert_with(|| json!({}))
+                .as_object_mut()
+                .context("config's \"mjolnir\" section must be a JSON object")?;
+            if let Some(path) = &self.tile_extract {
+                mjolnir.insert("tile_extract".to_string(), json!(path.display().to_string()));
+            }
+            if let Some(path) = &self.traffic_extract {
+                mjolnir.insert("traffic_extract".to_string(), json!(path.display().to_string()));
+            }
+            if let Some(path) = &self.tile_dir {
+                mjolnir.insert("tile_dir".to_string(), json!(path.display().to_string()));
+            }
+        }
+
+        if !self.costing_defaults.is_empty() {
+            let costing_options = root_map
+                .entry("costing_options")
+                .or_insert_with(|| json!({}))
+                .as_object_mut()
+                .context("config's \"costing_options\" section must be a JSON object")?;
+            for (costing, options) in self.costing_defaults {
+                costing_options.insert(costing, options);
+            }
+        }
+
+        Config::from_json(&root.to_string())
+    }
 }
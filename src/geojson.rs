@@ -0,0 +1,122 @@
+//! GeoJSON rendering for [`Response`], built on top of the typed views in [`crate::response_types`].
+//! Useful for dropping a route or isochrone straight onto a map library that speaks GeoJSON without
+//! pulling in a whole mapping SDK just to walk `proto::Api`.
+
+use crate::{
+    Error, LatLon,
+    actor::Response,
+    response_types::{IsochroneContour, RouteResult},
+};
+
+impl Response {
+    /// Renders a `route`/`optimized_route` response as a GeoJSON `FeatureCollection` string: one
+    /// `LineString` feature per leg, with `time_secs`, `length_m` and per-maneuver instructions as
+    /// properties. An `isochrone` response is already GeoJSON, so it's passed through unchanged
+    /// rather than being round-tripped through [`Response::route_result`].
+    pub fn to_geojson(&self) -> Result<String, Error> {
+        if let Response::Json(json) = self {
+            if is_feature_collection(json) {
+                return Ok(json.clone());
+            }
+        }
+        let route = self.route_result()?;
+        route_to_geojson(&route)
+    }
+
+    /// Renders a `matrix` response's echoed `sources`/`targets` locations as a GeoJSON
+    /// `FeatureCollection` of `Point` features, tagged with `role` (`"source"`/`"target"`) and
+    /// `index` properties. Only [`Response::Json`] carries the echoed locations.
+    pub fn matrix_geojson(&self) -> Result<String, Error> {
+        let Response::Json(json) = self else {
+            return Err(Error("matrix_geojson needs a Json response".into()));
+        };
+        let value: serde_json::Value = serde_json::from_str(json).map_err(|err| Error(err.to_string().into()))?;
+
+        let mut features = Vec::new();
+        collect_points(&value, "sources", "source", &mut features);
+        collect_points(&value, "targets", "target", &mut features);
+
+        let collection = serde_json::json!({"type": "FeatureCollection", "features": features});
+        serde_json::to_string(&collection).map_err(|err| Error(err.to_string().into()))
+    }
+}
+
+fn is_feature_collection(json: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(json)
+        .ok()
+        .and_then(|value| value.get("type").and_then(|v| v.as_str()).map(str::to_string))
+        .is_some_and(|type_name| type_name == "FeatureCollection")
+}
+
+fn route_to_geojson(route: &RouteResult) -> Result<String, Error> {
+    let features: Vec<serde_json::Value> = route
+        .legs
+        .iter()
+        .enumerate()
+        .map(|(leg_index, leg)| {
+            let instructions: Vec<&str> = leg.maneuvers.iter().map(|maneuver| maneuver.instruction.as_str()).collect();
+            serde_json::json!({
+                "type": "Feature",
+                "properties": {
+                    "leg_index": leg_index,
+                    "time_secs": leg.time_secs,
+                    "length_m": leg.length_m,
+                    "maneuver_instructions": instructions,
+                },
+                "geometry": {
+                    "type": "LineString",
+                    "coordinates": coords(&leg.shape),
+                },
+            })
+        })
+        .collect();
+
+    let collection = serde_json::json!({"type": "FeatureCollection", "features": features});
+    serde_json::to_string(&collection).map_err(|err| Error(err.to_string().into()))
+}
+
+/// Renders parsed [`IsochroneContour`]s back into a GeoJSON `FeatureCollection` string. Exposed for
+/// callers who already have contours (e.g. from [`Response::isochrone_features`]) and want GeoJSON
+/// without keeping the original response's JSON string around.
+pub fn isochrone_contours_to_geojson(contours: &[IsochroneContour]) -> Result<String, Error> {
+    let features: Vec<serde_json::Value> = contours
+        .iter()
+        .map(|contour| {
+            let rings: Vec<Vec<Vec<f64>>> = contour.rings.iter().map(|ring| coords(ring)).collect();
+            serde_json::json!({
+                "type": "Feature",
+                "properties": {
+                    "contour": contour.contour_value,
+                    "color": contour.color,
+                },
+                "geometry": {
+                    "type": "Polygon",
+                    "coordinates": rings,
+                },
+            })
+        })
+        .collect();
+
+    let collection = serde_json::json!({"type": "FeatureCollection", "features": features});
+    serde_json::to_string(&collection).map_err(|err| Error(err.to_string().into()))
+}
+
+fn coords(points: &[LatLon]) -> Vec<Vec<f64>> {
+    points.iter().map(|point| vec![point.1, point.0]).collect()
+}
+
+fn collect_points(value: &serde_json::Value, key: &str, role: &str, features: &mut Vec<serde_json::Value>) {
+    let Some(points) = value.get(key).and_then(|v| v.as_array()) else {
+        return;
+    };
+    for (index, point) in points.iter().enumerate() {
+        let (Some(lat), Some(lon)) = (point.get("lat").and_then(|v| v.as_f64()), point.get("lon").and_then(|v| v.as_f64())) else {
+            continue;
+        };
+        features.push(serde_json::json!({
+            "type": "Feature",
+            "properties": {"role": role, "index": index},
+            "geometry": {"type": "Point", "coordinates": [lon, lat]},
+        }));
+    }
+}
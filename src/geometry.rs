@@ -0,0 +1,135 @@
+//! Encoding/decoding helpers for the shape formats Valhalla accepts and returns: Google-style
+//! encoded polylines (used at precision 6 for most shapes, precision 5 for some legacy outputs)
+//! and GeoJSON `LineString` geometry.
+
+use crate::{LatLon, proto};
+
+/// Mean Earth radius in meters, used by [`haversine_distance_m`].
+pub(crate) const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+/// Great-circle distance between `a` and `b`, in meters, via the haversine formula.
+pub(crate) fn haversine_distance_m(a: LatLon, b: LatLon) -> f64 {
+    let (lat1, lat2) = (a.0.to_radians(), b.0.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (b.1 - a.1).to_radians();
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_M * h.sqrt().asin()
+}
+
+/// Encodes a sequence of points as a Google-style polyline, scaling each coordinate by `10^precision`
+/// before zig-zag encoding the delta from the previous point. Valhalla uses precision 6 for shapes
+/// and precision 5 for some legacy outputs (e.g. OSRM-compatible responses).
+pub fn encode_polyline(points: &[LatLon], precision: u32) -> String {
+    let factor = 10f64.powi(precision as i32);
+    let mut encoded = String::new();
+    let mut prev_lat = 0i64;
+    let mut prev_lon = 0i64;
+
+    for point in points {
+        let lat = (point.0 * factor).round() as i64;
+        let lon = (point.1 * factor).round() as i64;
+        encode_value(lat - prev_lat, &mut encoded);
+        encode_value(lon - prev_lon, &mut encoded);
+        prev_lat = lat;
+        prev_lon = lon;
+    }
+    encoded
+}
+
+fn encode_value(value: i64, out: &mut String) {
+    let mut value = value << 1;
+    if value < 0 {
+        value = !value;
+    }
+    while value >= 0x20 {
+        let chunk = (0x20 | (value & 0x1f)) as u8 + 63;
+        out.push(chunk as char);
+        value >>= 5;
+    }
+    out.push((value as u8 + 63) as char);
+}
+
+/// Decodes a Google-style polyline encoded at the given precision into `(lat, lon)` points.
+pub fn decode_polyline(encoded: &str, precision: u32) -> Vec<LatLon> {
+    let factor = 10f64.powi(precision as i32);
+    let mut points = Vec::new();
+    let mut lat = 0i64;
+    let mut lon = 0i64;
+    let mut chars = encoded.chars().peekable();
+
+    while chars.peek().is_some() {
+        let (Some(dlat), Some(dlon)) = (decode_value(&mut chars), decode_value(&mut chars)) else {
+            break;
+        };
+        lat += dlat;
+        lon += dlon;
+        points.push(LatLon(lat as f64 / factor, lon as f64 / factor));
+    }
+    points
+}
+
+fn decode_value(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<i64> {
+    let mut shift = 0;
+    let mut result: i64 = 0;
+    loop {
+        let byte = chars.next()? as i64 - 63;
+        result |= (byte & 0x1f) << shift;
+        shift += 5;
+        if byte < 0x20 {
+            break;
+        }
+    }
+    Some(if result & 1 != 0 { !(result >> 1) } else { result >> 1 })
+}
+
+/// Parses a GeoJSON `LineString` geometry object (`{"type": "LineString", "coordinates": [[lon, lat], ...]}`)
+/// into points, following GeoJSON's `[lon, lat]` coordinate order.
+pub fn from_geojson_linestring(geojson: &serde_json::Value) -> Option<Vec<LatLon>> {
+    let coordinates = geojson.get("coordinates")?.as_array()?;
+    coordinates
+        .iter()
+        .map(|coord| {
+            let coord = coord.as_array()?;
+            let lon = coord.first()?.as_f64()?;
+            let lat = coord.get(1)?.as_f64()?;
+            Some(LatLon(lat, lon))
+        })
+        .collect()
+}
+
+/// Sets `has_encoded_polyline`/`shape` on a trace request from real coordinates, so callers don't
+/// have to hand-build an encoded polyline string for [`Actor::trace_route`](crate::Actor::trace_route)
+/// or [`Actor::trace_attributes`](crate::Actor::trace_attributes).
+pub fn set_trace_shape(options: &mut proto::Options, points: &[LatLon]) {
+    options.has_encoded_polyline = Some(proto::options::HasEncodedPolyline::EncodedPolyline(
+        encode_polyline(points, 6),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_polyline5() {
+        let points = vec![LatLon(38.5, -120.2), LatLon(40.7, -120.95), LatLon(43.252, -126.453)];
+        let encoded = encode_polyline(&points, 5);
+        assert_eq!(encoded, "_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+
+        let decoded = decode_polyline(&encoded, 5);
+        for (expected, actual) in points.iter().zip(decoded.iter()) {
+            assert!((expected.0 - actual.0).abs() < 1e-5);
+            assert!((expected.1 - actual.1).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn parses_geojson_linestring() {
+        let geojson = serde_json::json!({
+            "type": "LineString",
+            "coordinates": [[-120.2, 38.5], [-120.95, 40.7]],
+        });
+        let points = from_geojson_linestring(&geojson).unwrap();
+        assert_eq!(points, vec![LatLon(38.5, -120.2), LatLon(40.7, -120.95)]);
+    }
+}
@@ -0,0 +1,145 @@
+//! A [`petgraph::visit`] adapter over [`GraphReader`] + [`CostingModel`], so the tiled road network
+//! can be fed directly into petgraph's generic algorithms (`dijkstra`, `kosaraju_scc`, ...) instead
+//! of hand-rolling traversal every time one is needed.
+//!
+//! Whole-graph algorithms that need to enumerate every node/edge up front (e.g. ones built on
+//! [`petgraph::visit::IntoNodeIdentifiers`]) aren't supported: a planet-scale tileset has no
+//! in-memory node/edge list to hand back. What's supported is everything built on per-node
+//! expansion ([`petgraph::visit::IntoNeighbors`]/[`petgraph::visit::IntoEdges`]), which covers
+//! `dijkstra` and other single-source searches.
+
+use petgraph::visit::{Data, EdgeRef as _, GraphBase, IntoEdgeReferences, IntoEdges, IntoNeighbors, VisitMap, Visitable};
+
+use crate::{CostingModel, GraphId, GraphReader};
+
+/// Adapter over [`GraphReader`] + [`CostingModel`] implementing petgraph's visitor traits, with
+/// [`GraphId`] as both the node and edge identifier.
+///
+/// As both fields are shared references, `ValhallaGraph` is `Copy`, matching how petgraph's own
+/// graph types are passed around (e.g. `petgraph::algo::dijkstra(graph, ...)` takes `graph` by
+/// value).
+#[derive(Clone, Copy)]
+pub struct ValhallaGraph<'a> {
+    reader: &'a GraphReader,
+    costing: &'a CostingModel,
+}
+
+impl<'a> ValhallaGraph<'a> {
+    /// Wraps `reader`, resolving neighbor and edge queries according to `costing`.
+    pub fn new(reader: &'a GraphReader, costing: &'a CostingModel) -> Self {
+        Self { reader, costing }
+    }
+
+    /// Accessible outbound edges of `node`, as [`GraphEdgeRef`]s. Empty if `node`'s tile isn't
+    /// loaded, the node doesn't exist, or [`CostingModel::node_accessible`] rejects the node.
+    fn edges_from(self, node: GraphId) -> Vec<GraphEdgeRef> {
+        let Some(tile) = self.reader.graph_tile(node.tile()) else {
+            return Vec::new();
+        };
+        let Some(node_info) = tile.node(node.id()) else {
+            return Vec::new();
+        };
+        if !self.costing.node_accessible(node_info) {
+            return Vec::new();
+        }
+
+        let begin_index = node_info.edge_index();
+        tile.node_edges(node_info)
+            .iter()
+            .enumerate()
+            .filter(|(_, edge)| self.costing.edge_accessible(edge))
+            .filter_map(|(offset, edge)| {
+                let edge_index = begin_index + offset as u32;
+                let edge_id = GraphId::from_parts(node.tile().level(), node.tile().tileid(), edge_index)?;
+                Some(GraphEdgeRef {
+                    source: node,
+                    target: edge.endnode(),
+                    edge_id,
+                    cost: self.costing.edge_cost(edge, &tile, 0).secs,
+                })
+            })
+            .collect()
+    }
+}
+
+/// One accessible directed edge, as handed to petgraph's per-edge visitor traits. Implements
+/// [`petgraph::visit::EdgeRef`] with [`Self::cost`] (seconds, via [`CostingModel::edge_cost`]) as
+/// the edge weight.
+#[derive(Debug, Clone, Copy)]
+pub struct GraphEdgeRef {
+    source: GraphId,
+    target: GraphId,
+    edge_id: GraphId,
+    cost: f32,
+}
+
+impl petgraph::visit::EdgeRef for GraphEdgeRef {
+    type NodeId = GraphId;
+    type EdgeId = GraphId;
+    type Weight = f32;
+
+    fn source(&self) -> GraphId {
+        self.source
+    }
+
+    fn target(&self) -> GraphId {
+        self.target
+    }
+
+    fn weight(&self) -> &f32 {
+        &self.cost
+    }
+
+    fn id(&self) -> GraphId {
+        self.edge_id
+    }
+}
+
+impl<'a> GraphBase for ValhallaGraph<'a> {
+    type NodeId = GraphId;
+    type EdgeId = GraphId;
+}
+
+impl<'a> Data for ValhallaGraph<'a> {
+    type NodeWeight = ();
+    type EdgeWeight = f32;
+}
+
+impl<'a> Visitable for ValhallaGraph<'a> {
+    type Map = std::collections::HashSet<GraphId>;
+
+    fn visit_map(&self) -> Self::Map {
+        std::collections::HashSet::new()
+    }
+
+    fn reset_map(&self, map: &mut Self::Map) {
+        map.clear();
+    }
+}
+
+impl<'a> IntoNeighbors for ValhallaGraph<'a> {
+    type Neighbors = std::vec::IntoIter<GraphId>;
+
+    fn neighbors(self, a: GraphId) -> Self::Neighbors {
+        self.edges_from(a).into_iter().map(|edge| edge.target).collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl<'a> IntoEdgeReferences for ValhallaGraph<'a> {
+    type EdgeRef = GraphEdgeRef;
+    type EdgeReferences = std::iter::Empty<GraphEdgeRef>;
+
+    /// Not supported: a tile-backed graph has no bounded edge set to enumerate up front. Use
+    /// [`IntoEdges::edges`] (what `petgraph::algo::dijkstra` and friends actually call) instead.
+    fn edge_references(self) -> Self::EdgeReferences {
+        std::iter::empty()
+    }
+}
+
+impl<'a> IntoEdges for ValhallaGraph<'a> {
+    type Edges = std::vec::IntoIter<GraphEdgeRef>;
+
+    fn edges(self, a: GraphId) -> Self::Edges {
+        self.edges_from(a).into_iter()
+    }
+}
@@ -0,0 +1,268 @@
+//! Splits a multimodal route response into typed [`Leg`]s (walk, bicycle, drive, transit), so
+//! transit-oriented planners can render a trip plan without walking the raw [`proto::Api`] tree
+//! and diffing `travel_mode` between consecutive maneuvers themselves.
+//!
+//! Transit legs require requesting [`proto::costing::Type::Multimodal`] costing with a `Transit`
+//! entry in [`proto::Options::costings`], mirroring how Valhalla's own `multimodal` action combines
+//! pedestrian and transit costing to hop between walking and riding.
+//! [`crate::Actor::transit_available`] is the cheaper check for whether a location has any transit
+//! coverage at all before committing to a full multimodal route.
+//!
+//! ```
+//! # fn call_multimodal_route(mut actor: valhalla::Actor) {
+//! use valhalla::proto;
+//!
+//! let request = proto::Options {
+//!     costing_type: proto::costing::Type::Multimodal as i32,
+//!     format: proto::options::Format::Pbf as i32,
+//!     costings: [(
+//!         proto::costing::Type::Transit as i32,
+//!         proto::Costing {
+//!             r#type: proto::costing::Type::Transit as i32,
+//!             ..Default::default()
+//!         },
+//!     )]
+//!     .into(),
+//!     locations: vec![
+//!         proto::Location {
+//!             ll: valhalla::LatLon(55.6086, 13.0005).into(),
+//!             ..Default::default()
+//!         },
+//!         proto::Location {
+//!             ll: valhalla::LatLon(55.5944, 13.0002).into(),
+//!             ..Default::default()
+//!         },
+//!     ],
+//!     ..Default::default()
+//! };
+//! let response = actor.route(&request).unwrap();
+//! let itinerary = valhalla::itinerary::Itinerary::from_response(&response).unwrap();
+//! println!("{:.0}s walking, {:.0}s on transit", itinerary.total_walk_time(), itinerary.total_transit_time());
+//! # }
+//! ```
+
+use crate::{LatLon, actor::Response, geometry, proto};
+
+/// How a [`Leg`] of the trip is traveled, mirroring Valhalla's `Maneuver::travel_mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TravelMode {
+    Drive,
+    Pedestrian,
+    Bicycle,
+    Transit(TransitType),
+}
+
+/// Vehicle type of a transit leg, numbered the same way as GTFS `route_type`, which is what
+/// Valhalla's `Maneuver::travel_type` holds for transit maneuvers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitType {
+    Tram,
+    Metro,
+    Rail,
+    Bus,
+    Ferry,
+    CableCar,
+    Gondola,
+    Funicular,
+    /// Any GTFS `route_type` this crate doesn't have a named variant for.
+    Other(u32),
+}
+
+impl TransitType {
+    fn from_travel_type(travel_type: u32) -> Self {
+        match travel_type {
+            0 => TransitType::Tram,
+            1 => TransitType::Metro,
+            2 => TransitType::Rail,
+            3 => TransitType::Bus,
+            4 => TransitType::Ferry,
+            5 => TransitType::CableCar,
+            6 => TransitType::Gondola,
+            7 => TransitType::Funicular,
+            other => TransitType::Other(other),
+        }
+    }
+}
+
+impl TravelMode {
+    fn from_maneuver(travel_mode: i32, travel_type: u32) -> Self {
+        match travel_mode {
+            0 => TravelMode::Drive,
+            1 => TravelMode::Pedestrian,
+            2 => TravelMode::Bicycle,
+            _ => TravelMode::Transit(TransitType::from_travel_type(travel_type)),
+        }
+    }
+}
+
+/// One stop a [`TransitDetails`] leg passes through, e.g. an intermediate station the rider
+/// doesn't board or alight at.
+#[derive(Debug, Clone, Default)]
+pub struct TransitStop {
+    pub name: String,
+    /// Scheduled arrival at this stop, as Valhalla's ISO 8601 local time string (empty if this is
+    /// the trip's first stop).
+    pub arrival_date_time: String,
+    /// Scheduled departure from this stop, as Valhalla's ISO 8601 local time string (empty if this
+    /// is the trip's last stop).
+    pub departure_date_time: String,
+}
+
+/// Transit-specific fields of a [`Leg`] whose [`TravelMode`] is [`TravelMode::Transit`], mirroring
+/// Valhalla's `TransitRouteInfo`.
+#[derive(Debug, Clone, Default)]
+pub struct TransitDetails {
+    /// Valhalla's stable identifier for the transit route, used to tell two rides on the same
+    /// [`TransitType`] apart (e.g. transferring from one bus route to another at a stop with no
+    /// walking maneuver in between).
+    pub onestop_id: String,
+    /// Short rider-facing route name, e.g. `"42"`.
+    pub route_short_name: String,
+    /// Long rider-facing route name, e.g. `"Downtown Express"`.
+    pub route_long_name: String,
+    /// Rider-facing destination text shown on the vehicle, e.g. `"42 Downtown"`.
+    pub headsign: String,
+    /// Stops the leg passes through, in travel order, including the boarding and alighting stops.
+    pub stops: Vec<TransitStop>,
+}
+
+/// A contiguous stretch of the trip traveled with a single [`TravelMode`], e.g. "walk to the bus
+/// stop" or "ride the 42 bus".
+#[derive(Debug, Clone)]
+pub struct Leg {
+    pub mode: TravelMode,
+    pub start: LatLon,
+    pub end: LatLon,
+    pub start_time: f64,
+    pub end_time: f64,
+    /// Decoded shape of this leg, a contiguous slice of the parent trip leg's geometry.
+    pub shape: Vec<LatLon>,
+    /// Maneuvers within this leg, in the same order as Valhalla returned them.
+    pub maneuvers: Vec<proto::trip_leg::Maneuver>,
+    /// Route name, headsign and stop-by-stop schedule, populated only when `mode` is
+    /// [`TravelMode::Transit`].
+    pub transit: Option<TransitDetails>,
+}
+
+/// A route broken into mode-homogeneous legs, ready to render without touching `proto::Api`.
+#[derive(Debug, Clone)]
+pub struct Itinerary {
+    pub legs: Vec<Leg>,
+}
+
+impl Itinerary {
+    /// Builds an itinerary from a multimodal `route` response, splitting each of Valhalla's own
+    /// trip legs further whenever the travel mode changes between consecutive maneuvers.
+    pub fn from_response(response: &Response) -> Option<Self> {
+        let Response::Pbf(api) = response else {
+            return None;
+        };
+        let trip = api.trip.as_ref()?;
+
+        let mut legs = Vec::new();
+        for trip_leg in &trip.legs {
+            let shape = geometry::decode_polyline(&trip_leg.shape, 6);
+            legs.extend(split_by_mode(trip_leg, &shape));
+        }
+        Some(Self { legs })
+    }
+
+    /// Total time, in seconds, spent in [`TravelMode::Pedestrian`] legs.
+    pub fn total_walk_time(&self) -> f64 {
+        self.time_in_mode(|mode| matches!(mode, TravelMode::Pedestrian))
+    }
+
+    /// Total time, in seconds, spent in [`TravelMode::Transit`] legs, across all transit types.
+    pub fn total_transit_time(&self) -> f64 {
+        self.time_in_mode(|mode| matches!(mode, TravelMode::Transit(_)))
+    }
+
+    fn time_in_mode(&self, matches_mode: impl Fn(&TravelMode) -> bool) -> f64 {
+        self.legs
+            .iter()
+            .filter(|leg| matches_mode(&leg.mode))
+            .map(|leg| leg.end_time - leg.start_time)
+            .sum()
+    }
+}
+
+/// Groups a trip leg's maneuvers into mode-homogeneous [`Leg`]s, carrying along the slice of the
+/// decoded shape each group spans.
+fn split_by_mode(trip_leg: &proto::TripLeg, shape: &[LatLon]) -> Vec<Leg> {
+    let mut legs: Vec<Leg> = Vec::new();
+
+    for maneuver in &trip_leg.maneuvers {
+        let mode = TravelMode::from_maneuver(maneuver.travel_mode, maneuver.travel_type);
+        let begin = maneuver.begin_shape_index as usize;
+        let end = (maneuver.end_shape_index as usize).min(shape.len().saturating_sub(1));
+
+        let same_mode_as_last = legs.last().is_some_and(|leg| leg.mode == mode && same_transit_route(leg, maneuver));
+        if same_mode_as_last {
+            let leg = legs.last_mut().expect("checked above");
+            leg.end = shape.get(end).copied().unwrap_or(leg.end);
+            leg.end_time += maneuver.time;
+            leg.shape.extend(shape.get(begin..=end).unwrap_or_default().iter().skip(1));
+            if let Some(transit) = &mut leg.transit {
+                transit.stops.extend(transit_stops(maneuver));
+            }
+            leg.maneuvers.push(maneuver.clone());
+        } else {
+            let start_time = legs.last().map_or(0.0, |leg| leg.end_time);
+            legs.push(Leg {
+                mode,
+                start: shape.get(begin).copied().unwrap_or(LatLon(0.0, 0.0)),
+                end: shape.get(end).copied().unwrap_or(LatLon(0.0, 0.0)),
+                start_time,
+                end_time: start_time + maneuver.time,
+                shape: shape.get(begin..=end).unwrap_or_default().to_vec(),
+                maneuvers: vec![maneuver.clone()],
+                transit: transit_details(maneuver, mode),
+            });
+        }
+    }
+    legs
+}
+
+/// Whether `maneuver` continues the same transit ride as `leg`, so two consecutive rides on the
+/// same [`TransitType`] (e.g. bus-to-bus transfer with no walking in between) still split into
+/// separate legs instead of being merged under the first ride's route name.
+fn same_transit_route(leg: &Leg, maneuver: &proto::trip_leg::Maneuver) -> bool {
+    let Some(transit) = &leg.transit else {
+        return true; // not a transit leg, mode equality already decided it
+    };
+    maneuver
+        .transit_route_info
+        .as_ref()
+        .is_some_and(|info| info.onestop_id == transit.onestop_id)
+}
+
+/// Builds a [`TransitDetails`] for the first maneuver of a new transit [`Leg`], or `None` for any
+/// other travel mode.
+fn transit_details(maneuver: &proto::trip_leg::Maneuver, mode: TravelMode) -> Option<TransitDetails> {
+    if !matches!(mode, TravelMode::Transit(_)) {
+        return None;
+    }
+    let info = maneuver.transit_route_info.as_ref()?;
+    Some(TransitDetails {
+        onestop_id: info.onestop_id.clone(),
+        route_short_name: info.short_name.clone(),
+        route_long_name: info.long_name.clone(),
+        headsign: info.headsign.clone(),
+        stops: transit_stops(maneuver),
+    })
+}
+
+/// Extracts the stop-by-stop schedule Valhalla attaches to a transit maneuver.
+fn transit_stops(maneuver: &proto::trip_leg::Maneuver) -> Vec<TransitStop> {
+    let Some(info) = maneuver.transit_route_info.as_ref() else {
+        return Vec::new();
+    };
+    info.transit_stops
+        .iter()
+        .map(|stop| TransitStop {
+            name: stop.name.clone(),
+            arrival_date_time: stop.arrival_date_time.clone(),
+            departure_date_time: stop.departure_date_time.clone(),
+        })
+        .collect()
+}
@@ -8,12 +8,36 @@ use prost::Message;
 
 mod actor;
 mod config;
+pub mod geometry;
+mod geojson;
+mod graph;
+pub mod itinerary;
+mod locate;
+mod matrix;
+mod navigation;
 pub mod proto;
+mod reachability;
+pub mod response_types;
+mod route;
+mod shortcut;
+pub mod vrp;
+
+pub use locate::EdgeLocation;
 
 pub use actor::Actor;
 pub use actor::Response;
+pub use geojson::isochrone_contours_to_geojson;
+pub use graph::{GraphEdgeRef, ValhallaGraph};
+pub use matrix::MatrixCell;
+pub use navigation::NavigationSession;
+pub use navigation::NavigationState;
+pub use navigation::resample_shape;
+pub use reachability::{CostBudget, Reachability, ReachableEdge};
+pub use route::Route;
 pub use config::Config;
+pub use ffi::AccessRestriction;
 pub use ffi::AdminInfo;
+pub use ffi::Cost;
 pub use ffi::DirectedEdge;
 pub use ffi::EdgeInfo;
 pub use ffi::EdgeUse;
@@ -21,6 +45,7 @@ pub use ffi::GraphId;
 pub use ffi::GraphLevel;
 pub use ffi::NodeInfo;
 pub use ffi::NodeTransition;
+pub use ffi::RestrictionType;
 pub use ffi::RoadClass;
 pub use ffi::TimeZoneInfo;
 pub use ffi::TrafficTile;
@@ -121,6 +146,46 @@ mod ffi {
         kInvalid = 8,
     }
 
+    /// Kind of restriction an [`AccessRestriction`] represents, determining how its `value` field
+    /// should be interpreted.
+    #[namespace = "valhalla::baldr"]
+    #[cxx_name = "AccessRestrictionType"]
+    #[repr(u8)]
+    #[derive(Debug)]
+    enum RestrictionType {
+        MaxHeight = 0,
+        MaxWidth = 1,
+        MaxLength = 2,
+        MaxWeight = 3,
+        MaxAxleLoad = 4,
+        MaxGrossWeight = 5,
+        MaxAxles = 6,
+        Hazmat = 7,
+        TimeAllowed = 8,
+        TimeDenied = 9,
+        Destination = 10,
+    }
+
+    /// A dimension/weight/time-of-day/destination restriction stored for an edge, e.g. `hgv=no`
+    /// or a `maxheight` tag. Retrieved via [`crate::GraphTile::access_restrictions()`].
+    #[derive(Clone, Copy, Debug)]
+    struct AccessRestriction {
+        /// Index of the directed edge this restriction applies to, within the tile.
+        edge_index: u32,
+        /// Travel modes this restriction applies to. Bit mask using [`crate::Access`] constants.
+        modes: u16,
+        /// Kind of restriction, determining how [`Self::value`] should be interpreted.
+        restriction_type: RestrictionType,
+        /// Restriction value, whose unit depends on [`Self::restriction_type`]:
+        /// - `MaxHeight`/`MaxWidth`/`MaxLength`: centimeters.
+        /// - `MaxWeight`/`MaxGrossWeight`/`MaxAxleLoad`: hundredths of a metric ton (so `80` is 800 kg).
+        /// - `MaxAxles`: axle count.
+        /// - `Hazmat`: `0`/`1`, whether hazardous materials are restricted.
+        /// - `TimeAllowed`/`TimeDenied`: a packed `TimeDomain` bitfield (days/hours the restriction is in effect).
+        /// - `Destination`: unused, always `0`.
+        value: u64,
+    }
+
     /// Directed edge within the graph.
     struct DirectedEdge {
         // With this definition and cxx's magic it becomes possible to do pointer arithmetic properly,
@@ -255,6 +320,14 @@ mod ffi {
         fn node_transitions<'a>(tile: &'a GraphTile, node: &NodeInfo) -> &'a [NodeTransition];
         fn node_latlon(tile: &GraphTile, node: &NodeInfo) -> LatLon;
         fn admininfo(tile: &GraphTile, index: u32) -> Result<AdminInfo>;
+        /// Dimension/weight/time-of-day/destination restrictions stored for the edge at `edge_index`,
+        /// filtered to those applying to any mode in `access_modes` (an [`crate::Access`] bitmask).
+        fn access_restrictions(tile: &GraphTile, edge_index: u32, access_modes: u16) -> Vec<AccessRestriction>;
+        /// Decoded weekly predicted-speed profile (5-minute buckets starting Sunday 00:00) stored
+        /// for the edge, found via its DCT coefficient block in the tile's predicted-speed
+        /// section and run through the same DCT-III inverse as [`decode_weekly_speeds`]. Errors if
+        /// the edge has no predicted-speed profile (see [`DirectedEdge::has_predicted_speed`]).
+        fn predicted_speeds(tile: &GraphTile, de: &DirectedEdge) -> Result<Vec<f32>>;
         unsafe fn IsClosed(self: &GraphTile, de: *const DirectedEdge) -> bool;
         unsafe fn GetSpeed(
             self: &GraphTile,
@@ -373,8 +446,15 @@ mod ffi {
         fn free_flow_speed(self: &DirectedEdge) -> u32;
         /// Constrained flow speed (typical speed during day, from 7am to 7pm) in km/h for this edge.
         fn constrained_flow_speed(self: &DirectedEdge) -> u32;
+        /// Whether this edge has a stored weekly predicted-speed (historical traffic) profile,
+        /// readable via [`crate::GraphTile::predicted_speeds()`].
+        fn has_predicted_speed(self: &DirectedEdge) -> bool;
         /// Whether this edge is a shortcut edge.
         fn is_shortcut(self: &DirectedEdge) -> bool;
+        /// For a base-level edge, whether it is superseded by a shortcut edge (i.e. collapsed into
+        /// one when the hierarchy above this level was built). Used by [`crate::GraphReader::recover_shortcut()`]
+        /// to recognize the base edges a shortcut supersedes.
+        fn superseded(self: &DirectedEdge) -> bool;
         /// Whether this directed edge ends in a different tile.
         fn leaves_tile(self: &DirectedEdge) -> bool;
 
@@ -449,6 +529,17 @@ mod ffi {
         fn decode_weekly_speeds(encoded: &str) -> Result<Vec<f32>>;
     }
 
+    /// Cost of a graph traversal: `cost` is what path-finding compares (it can include penalties
+    /// on top of travel time), `secs` is the actual elapsed time in seconds. Returned by
+    /// [`crate::CostingModel::edge_cost`]/[`crate::CostingModel::transition_cost`].
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Cost {
+        /// Cost used for path-finding comparisons; not necessarily seconds.
+        cost: f32,
+        /// Elapsed time in seconds.
+        secs: f32,
+    }
+
     unsafe extern "C++" {
         include!("valhalla/src/costing.hpp");
 
@@ -457,6 +548,29 @@ mod ffi {
         #[cxx_name = "Allowed"]
         unsafe fn NodeAllowed(self: &DynamicCost, node: *const NodeInfo) -> bool;
         unsafe fn IsAccessible(self: &DynamicCost, edge: *const DirectedEdge) -> bool;
+        /// Like [`Self::IsAccessible`], but also evaluates any `TimeAllowed`/`TimeDenied`
+        /// [`AccessRestriction`] `TimeDomain`s active at `seconds_of_week` (week starting Sunday
+        /// 00:00, matching the predicted-traffic convention) against the edge, e.g.
+        /// `motor_vehicle:conditional` tags or time-denied truck restrictions. `pred_edge` is the
+        /// previously traversed edge (null if `edge` is the first of a route), needed to evaluate
+        /// restrictions that depend on the incoming direction.
+        #[cxx_name = "Allowed"]
+        unsafe fn EdgeAllowed(
+            self: &DynamicCost,
+            edge: *const DirectedEdge,
+            tile: &GraphTile,
+            pred_edge: *const DirectedEdge,
+            seconds_of_week: u32,
+        ) -> bool;
+        /// Cost of traversing `edge`, evaluated at `seconds_of_week` (local time-of-week) for
+        /// costing models with time-dependent speeds or restrictions. When this model's options
+        /// (e.g. `hgv_no_access_penalty`) configure a restriction as "soft" (below Valhalla's
+        /// 12-hour hard-restriction sentinel), an edge that would otherwise fail `IsAccessible` is
+        /// instead allowed with the configured penalty folded into [`Cost::cost`].
+        unsafe fn EdgeCost(self: &DynamicCost, edge: *const DirectedEdge, tile: &GraphTile, seconds_of_week: u32) -> Cost;
+        /// Extra cost of transitioning from `pred` onto `edge` at `node`, e.g. turn penalties,
+        /// stop impacts, or maneuver penalties.
+        unsafe fn TransitionCost(self: &DynamicCost, edge: *const DirectedEdge, node: *const NodeInfo, pred: *const DirectedEdge) -> Cost;
 
         /// Creates a new costing model from the given serialized [`crate::proto::Costing`] protobuf object.
         fn new_cost(costing: &[u8]) -> Result<SharedPtr<DynamicCost>>;
@@ -610,7 +724,12 @@ impl From<LatLon> for Option<proto::LatLng> {
 /// N.B.: It is better to clone `GraphReader` instances rather than creating new ones from the same
 /// configuration to avoid duplicate memory mappings (up to 80GB+ per instance for planetary tilesets).
 #[derive(Clone)]
-pub struct GraphReader(cxx::SharedPtr<ffi::TileSet>);
+pub struct GraphReader {
+    tileset: cxx::SharedPtr<ffi::TileSet>,
+    /// Per-tile edge-segment spatial index used by [`GraphReader::locate`], built lazily and
+    /// shared across clones so repeated `locate` calls across threads stay `O(log n)`.
+    edge_rtree_cache: std::sync::Arc<std::sync::Mutex<std::collections::HashMap<GraphId, std::sync::Arc<locate::EdgeRTree>>>>,
+}
 
 impl GraphReader {
     /// Creates a new GraphReader from the given Valhalla configuration, parsed into a [`Config`].
@@ -624,22 +743,25 @@ impl GraphReader {
     /// let reader = valhalla::GraphReader::new(&config);
     /// ```
     pub fn new(config: &Config) -> Result<Self, Error> {
-        Ok(Self(ffi::new_tileset(config.inner())?))
+        Ok(Self {
+            tileset: ffi::new_tileset(config.inner())?,
+            edge_rtree_cache: Default::default(),
+        })
     }
 
     /// Latest OSM changeset ID (or the maximum OSM Node/Way/Relation ID) in the OSM PBF file used to build the tileset.
     pub fn dataset_id(&self) -> u64 {
-        self.0.dataset_id()
+        self.tileset.dataset_id()
     }
 
     /// List all tiles in the tileset.
     pub fn tiles(&self) -> Vec<GraphId> {
-        self.0.tiles()
+        self.tileset.tiles()
     }
 
     /// List all tiles in the bounding box for a given hierarchy level in the tileset.
     pub fn tiles_in_bbox(&self, min: LatLon, max: LatLon, level: GraphLevel) -> Vec<GraphId> {
-        self.0.tiles_in_bbox(
+        self.tileset.tiles_in_bbox(
             min.0 as f32,
             min.1 as f32,
             max.0 as f32,
@@ -662,12 +784,23 @@ impl GraphReader {
 
     /// Retrieves the graph tile data for a given [`GraphId`] if it exists in the tileset.
     pub fn graph_tile(&self, id: GraphId) -> Option<GraphTile> {
-        GraphTile::new(self.0.get_graph_tile(id))
+        GraphTile::new(self.tileset.get_graph_tile(id))
     }
 
     /// Retrieves the live traffic tile data for a given [`GraphId`] if it exists in the tileset.
     pub fn traffic_tile(&self, id: GraphId) -> Option<ffi::TrafficTile> {
-        self.0.get_traffic_tile(id).ok()
+        self.tileset.get_traffic_tile(id).ok()
+    }
+
+    /// Applies a batch of live-traffic updates to `tile`'s traffic tile in one call. A no-op if
+    /// `tile` has no traffic tile (e.g. the tileset was built without traffic support).
+    pub fn update_traffic(&self, tile: GraphId, updates: impl Iterator<Item = (u32, LiveTraffic)>) {
+        let Some(traffic_tile) = self.traffic_tile(tile) else {
+            return;
+        };
+        for (edge_index, traffic) in updates {
+            traffic_tile.write_edge_traffic(edge_index, traffic);
+        }
     }
 }
 
@@ -778,6 +911,41 @@ impl GraphTile {
         ffi::edgeinfo(&self.0, de)
     }
 
+    /// Finds every directed edge index in this tile whose [`EdgeInfo::way_id`] is `way_id`. A
+    /// single OSM way typically becomes several directed edges (one per direction, and split at
+    /// intersections or tile boundaries), so this can return more than one index, e.g. to resolve
+    /// which edges a way-keyed traffic feed's updates apply to before calling
+    /// [`GraphReader::update_traffic`].
+    pub fn edges_for_way(&self, way_id: u64) -> Vec<u32> {
+        self.directededges()
+            .iter()
+            .enumerate()
+            .filter(|(_, edge)| self.edgeinfo(edge).way_id == way_id)
+            .map(|(index, _)| index as u32)
+            .collect()
+    }
+
+    /// Dimension/weight/time-of-day/destination restrictions stored for the edge at `edge_index`
+    /// (its position within [`Self::directededges()`]), filtered to those applying to any mode in
+    /// `access_modes`. This is what truck routing uses to evaluate `hgv=no`, `maxheight`, and
+    /// similar tags without re-deriving them from the costing model.
+    pub fn access_restrictions(&self, edge_index: u32, access_modes: Access) -> Vec<ffi::AccessRestriction> {
+        ffi::access_restrictions(&self.0, edge_index, access_modes.bits())
+    }
+
+    /// Decoded weekly predicted-speed (historical traffic) profile for `de`: 2016 km/h values, one
+    /// per 5-minute interval covering a full week starting from Sunday 00:00. `None` if `de` has no
+    /// predicted-speed profile stored (see [`ffi::DirectedEdge::has_predicted_speed`]).
+    ///
+    /// Reuses the same DCT-III inverse as [`decode_weekly_speeds`], returning `f32` rather than raw
+    /// bytes so round-tripping through [`encode_weekly_speeds`]/[`decode_weekly_speeds`] stays
+    /// consistent: this is the same 2016-value profile those functions work with, just read back
+    /// from a built tile instead of a CSV.
+    pub fn predicted_speeds(&self, de: &ffi::DirectedEdge) -> Option<Vec<f32>> {
+        debug_assert!(ref_within_slice(self.directededges(), de), "Wrong tile");
+        ffi::predicted_speeds(&self.0, de).ok()
+    }
+
     /// Edge's live traffic speed in km/h if available. Returns `Some(0)` if the edge is closed due to traffic.
     pub fn live_speed(&self, de: &ffi::DirectedEdge) -> Option<u32> {
         debug_assert!(ref_within_slice(self.directededges(), de), "Wrong tile");
@@ -825,6 +993,42 @@ impl GraphTile {
         };
         (speed, SpeedSources::from_bits_retain(flow_sources))
     }
+
+    /// Like [`Self::edge_speed`], but takes a UTC `unix_timestamp` and resolves the local
+    /// second-of-week itself, via `node`'s [`NodeInfo::timezone()`] and [`TimeZoneInfo::from_id`],
+    /// instead of making the caller compute `second_of_week` in the correct local timezone.
+    ///
+    /// `node` must be the edge's start node (the one `de` departs from), since that's whose
+    /// timezone the edge's posted/historical speeds are keyed to. Falls back to UTC (offset `0`)
+    /// if the node's timezone id doesn't resolve.
+    pub fn local_edge_speed(
+        &self,
+        de: &ffi::DirectedEdge,
+        node: &ffi::NodeInfo,
+        unix_timestamp: u64,
+        speed_sources: SpeedSources,
+        is_truck: bool,
+    ) -> (u32, SpeedSources) {
+        debug_assert!(ref_within_slice(self.nodes(), node), "Wrong tile");
+        let second_of_week = self.local_second_of_week(node, unix_timestamp);
+        self.edge_speed(de, speed_sources, is_truck, second_of_week, 0)
+    }
+
+    /// Converts `unix_timestamp` to a second-of-week local to `node` (week starting Sunday 00:00,
+    /// matching the predicted-traffic convention), resolving the timezone offset via
+    /// [`node`](ffi::NodeInfo)'s [`timezone()`](ffi::NodeInfo::timezone) index and
+    /// [`TimeZoneInfo::from_id`]. Used by [`Self::local_edge_speed`] and
+    /// [`crate::CostingModel::edge_allowed_at`] to get time-dependent queries to agree on what
+    /// "local time" means for a node.
+    pub fn local_second_of_week(&self, node: &ffi::NodeInfo, unix_timestamp: u64) -> u64 {
+        debug_assert!(ref_within_slice(self.nodes(), node), "Wrong tile");
+        let offset_seconds = TimeZoneInfo::from_id(node.timezone(), unix_timestamp)
+            .map(|tz| tz.offset_seconds)
+            .unwrap_or(0);
+        let local_timestamp = unix_timestamp as i64 + offset_seconds as i64;
+        const SECONDS_PER_WEEK: i64 = 7 * 24 * 60 * 60;
+        local_timestamp.rem_euclid(SECONDS_PER_WEEK) as u64
+    }
 }
 
 impl DirectedEdge {
@@ -841,6 +1045,22 @@ impl DirectedEdge {
     }
 }
 
+impl EdgeInfo {
+    /// Decodes [`Self::shape`] into points. Edge shapes are stored as polyline6 (precision 6),
+    /// matching Valhalla's own route responses; use [`Self::encoded_shape`] to re-encode at a
+    /// different precision (e.g. 5, for clients that expect the standard Google polyline
+    /// precision).
+    pub fn decoded_shape(&self) -> Vec<LatLon> {
+        geometry::decode_polyline(&self.shape, 6)
+    }
+
+    /// Re-encodes [`Self::shape`] as a Google/OSRM-style polyline at the given `precision`, e.g.
+    /// `5` for standard web map clients or `6` to match Valhalla's own route responses.
+    pub fn encoded_shape(&self, precision: u32) -> String {
+        geometry::encode_polyline(&self.decoded_shape(), precision)
+    }
+}
+
 impl NodeInfo {
     /// Returns the range of edge indices for this node's outbound edges.
     ///
@@ -995,6 +1215,50 @@ impl TrafficTile {
     pub fn write_edge_traffic(&self, edge_index: u32, traffic: LiveTraffic) {
         let _ = ffi::write_edge_traffic(self, edge_index, traffic.0);
     }
+
+    /// Iterates over every edge in the tile that has live traffic data, yielding `(edge_index,
+    /// LiveTraffic)` pairs in index order. Edges still at [`LiveTraffic::UNKNOWN`] (never written)
+    /// are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, LiveTraffic)> + '_ {
+        (0..self.edge_count())
+            .filter_map(move |edge_index| self.edge_traffic(edge_index).map(|traffic| (edge_index, traffic)))
+            .filter(|(_, traffic)| *traffic != LiveTraffic::UNKNOWN)
+    }
+}
+
+/// How strictly [`CostingModel::with_ignored_restrictions`] should honor access restrictions,
+/// mapped onto Valhalla's `ignore_*` costing options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IgnoreRestrictions {
+    /// Honor every restriction, the same as [`CostingModel::new`]. The default.
+    #[default]
+    None,
+    /// Ignore access restrictions that aren't specific to vehicles (e.g. dimensional or
+    /// time-of-day limits irrelevant to the travel mode), while still respecting hard vehicular
+    /// restrictions, oneways, and closures.
+    NonVehicular,
+    /// Ignore access restrictions, oneways, and closures altogether. Useful for map-matching a
+    /// trace recorded against a road that's since been closed or restricted.
+    All,
+}
+
+impl IgnoreRestrictions {
+    /// Overwrites `options`'s `ignore_*` fields to match this mode.
+    fn apply(self, options: &mut proto::costing::Options) {
+        let (ignore_non_vehicular, ignore_everything) = match self {
+            IgnoreRestrictions::None => (false, false),
+            IgnoreRestrictions::NonVehicular => (true, false),
+            IgnoreRestrictions::All => (true, true),
+        };
+        options.has_ignore_non_vehicular_restrictions = Some(
+            proto::costing::options::HasIgnoreNonVehicularRestrictions::IgnoreNonVehicularRestrictions(ignore_non_vehicular),
+        );
+        options.has_ignore_restrictions =
+            Some(proto::costing::options::HasIgnoreRestrictions::IgnoreRestrictions(ignore_everything));
+        options.has_ignore_oneways = Some(proto::costing::options::HasIgnoreOneways::IgnoreOneways(ignore_everything));
+        options.has_ignore_closures = Some(proto::costing::options::HasIgnoreClosures::IgnoreClosures(ignore_everything));
+        options.has_ignore_access = Some(proto::costing::options::HasIgnoreAccess::IgnoreAccess(ignore_everything));
+    }
 }
 
 /// A [costing model] that evaluates edge traversal costs and accessibility for different travel modes
@@ -1057,6 +1321,33 @@ impl CostingModel {
         Ok(Self(ffi::new_cost(&buf)?))
     }
 
+    /// Like [`Self::new`], but with `ignore` applied on top of the type's default options — see
+    /// [`IgnoreRestrictions`] for what each mode relaxes.
+    ///
+    /// Restriction handling is baked into the underlying [`ffi::DynamicCost`] at construction time
+    /// (Valhalla has no API to toggle it per accessibility check), so comparing strict vs. relaxed
+    /// accessibility for the same edge means keeping one `CostingModel` per mode around, e.g. to
+    /// tell a hard closure from one a caller has chosen to ignore.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valhalla::{CostingModel, IgnoreRestrictions, proto};
+    ///
+    /// let cost_model =
+    ///     CostingModel::with_ignored_restrictions(proto::costing::Type::Auto, IgnoreRestrictions::NonVehicular)
+    ///         .unwrap();
+    /// ```
+    pub fn with_ignored_restrictions(costing_type: proto::costing::Type, ignore: IgnoreRestrictions) -> Result<Self, Error> {
+        let mut options = proto::costing::Options::default();
+        ignore.apply(&mut options);
+        Self::with_options(&proto::Costing {
+            r#type: costing_type as i32,
+            has_options: Some(proto::costing::HasOptions::Options(options)),
+            ..Default::default()
+        })
+    }
+
     /// Checks if the node is accessible according to this costing model.
     ///
     /// Node access can be restricted by bollards, gates, or access restrictions
@@ -1073,6 +1364,53 @@ impl CostingModel {
     pub fn edge_accessible(&self, edge: &ffi::DirectedEdge) -> bool {
         unsafe { self.0.IsAccessible(edge as *const ffi::DirectedEdge) }
     }
+
+    /// Like [`Self::edge_accessible`], but also evaluates time-dependent access restrictions (e.g.
+    /// `motor_vehicle:conditional` tags, time-denied truck restrictions) active at
+    /// `seconds_of_week`, as computed by [`crate::GraphTile::local_second_of_week`] for the
+    /// departure time and the edge's begin node. `pred_edge` is the previously traversed edge, or
+    /// `None` if `edge` is the first of a route.
+    pub fn edge_allowed_at(
+        &self,
+        edge: &ffi::DirectedEdge,
+        tile: &GraphTile,
+        pred_edge: Option<&ffi::DirectedEdge>,
+        seconds_of_week: u32,
+    ) -> bool {
+        unsafe {
+            self.0.EdgeAllowed(
+                edge as *const ffi::DirectedEdge,
+                &tile.0,
+                pred_edge.map_or(std::ptr::null(), |e| e as *const ffi::DirectedEdge),
+                seconds_of_week,
+            )
+        }
+    }
+
+    /// Cost of traversing `edge` according to this costing model, at `second_of_week` (seconds
+    /// since local Sunday 00:00, in the same convention as [`crate::GraphTile::edge_speed`] and
+    /// [`crate::GraphTile::local_second_of_week`]) for costing models with time-dependent speeds
+    /// or restrictions.
+    ///
+    /// Soft-restriction penalties (e.g. a truck costing's `hgv_no_access_penalty` set below
+    /// Valhalla's 12-hour hard-restriction sentinel) are applied automatically here once
+    /// configured through [`Self::with_options`]; there's nothing extra to do on the Rust side.
+    pub fn edge_cost(&self, edge: &ffi::DirectedEdge, tile: &GraphTile, second_of_week: u64) -> ffi::Cost {
+        unsafe { self.0.EdgeCost(edge as *const ffi::DirectedEdge, &tile.0, second_of_week as u32) }
+    }
+
+    /// Extra cost of transitioning from `pred` onto `edge` at `node`, e.g. turn penalties, stop
+    /// impacts, or maneuver penalties. Added on top of [`Self::edge_cost`] when accumulating a
+    /// route's total cost.
+    pub fn transition_cost(&self, edge: &ffi::DirectedEdge, node: &ffi::NodeInfo, pred: &ffi::DirectedEdge) -> ffi::Cost {
+        unsafe {
+            self.0.TransitionCost(
+                edge as *const ffi::DirectedEdge,
+                node as *const ffi::NodeInfo,
+                pred as *const ffi::DirectedEdge,
+            )
+        }
+    }
 }
 
 /// Checks if the given reference points to an item within the given slice.
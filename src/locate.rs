@@ -0,0 +1,260 @@
+//! Map-matching/snapping support: turning a raw [`LatLon`] into a position on the road graph.
+//!
+//! Implemented the way SUMO's `convertCartesianToRoadMap` does: gather candidate tiles via
+//! [`GraphReader::tiles_in_bbox`] over a small bounding box around the query point, then search
+//! each tile's edge shapes for the closest point to the query. To avoid rescanning a tile's edges
+//! on every call, an [`rstar::RTree`] of edge segment AABBs is built and cached per tile on first
+//! query.
+
+use std::sync::Arc;
+
+use crate::{CostingModel, GraphId, GraphLevel, GraphReader, GraphTile, LatLon, geometry};
+
+/// Mean Earth radius in meters, used for the local equirectangular projection each tile's
+/// [`EdgeRTree`] is built in.
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+/// A directed edge found near a [`GraphReader::locate`] query point.
+#[derive(Debug, Clone, Copy)]
+pub struct EdgeLocation {
+    /// `GraphId` of the located directed edge.
+    pub edge_id: GraphId,
+    /// Point on the edge's shape closest to the query point.
+    pub projected_point: LatLon,
+    /// Position of [`Self::projected_point`] along the edge, from `0.0` (start) to `1.0` (end).
+    pub distance_along_edge: f64,
+    /// Perpendicular distance from the query point to [`Self::projected_point`], in meters.
+    pub distance_m: f64,
+}
+
+/// One segment of a decoded edge shape, projected into a tile-local planar coordinate system for
+/// indexing in an [`rstar::RTree`].
+struct EdgeSegment {
+    edge_index: u32,
+    a: (f64, f64),
+    b: (f64, f64),
+    /// Cumulative shape distance (in meters) from the edge start to `a`.
+    distance_to_a: f64,
+    /// Total length of the edge's decoded shape, in meters.
+    edge_length: f64,
+}
+
+impl rstar::RTreeObject for EdgeSegment {
+    type Envelope = rstar::AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        rstar::AABB::from_corners(
+            [self.a.0.min(self.b.0), self.a.1.min(self.b.1)],
+            [self.a.0.max(self.b.0), self.a.1.max(self.b.1)],
+        )
+    }
+}
+
+impl rstar::PointDistance for EdgeSegment {
+    fn distance_2(&self, point: &[f64; 2]) -> f64 {
+        let (_, distance_2) = project_onto_segment(*point, self.a, self.b);
+        distance_2
+    }
+}
+
+/// Per-tile spatial index of edge segments, cached by [`GraphReader`] so repeated `locate` calls
+/// over the same tile are `O(log n)` instead of rescanning every edge's shape.
+pub(crate) struct EdgeRTree {
+    /// Anchor point the tile's local planar coordinates are projected around.
+    anchor: LatLon,
+    tree: rstar::RTree<EdgeSegment>,
+}
+
+impl EdgeRTree {
+    fn build(tile: &GraphTile) -> Self {
+        let anchor = tile
+            .nodes()
+            .first()
+            .map(|node| tile.node_latlon(node))
+            .unwrap_or(LatLon(0.0, 0.0));
+
+        let mut segments = Vec::new();
+        for (edge_index, edge) in tile.directededges().iter().enumerate() {
+            let shape = geometry::decode_polyline(&tile.edgeinfo(edge).shape, 6);
+            if shape.len() < 2 {
+                continue;
+            }
+
+            let local: Vec<(f64, f64)> = shape.iter().map(|point| to_local(anchor, *point)).collect();
+            let mut distance_to_a = 0.0;
+            for pair in local.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                let segment_length = ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt();
+                segments.push(EdgeSegment {
+                    edge_index: edge_index as u32,
+                    a,
+                    b,
+                    distance_to_a,
+                    edge_length: 0.0, // filled in below once the full edge length is known
+                });
+                distance_to_a += segment_length;
+            }
+
+            // Now that the edge's total length is known, back-fill it into the segments just pushed.
+            let edge_length = distance_to_a;
+            for segment in segments.iter_mut().rev().take(local.len() - 1) {
+                segment.edge_length = edge_length;
+            }
+        }
+
+        Self {
+            anchor,
+            tree: rstar::RTree::bulk_load(segments),
+        }
+    }
+}
+
+/// Projects `point` onto the local planar coordinates an [`EdgeRTree`] was built in, using an
+/// equirectangular approximation centered on `anchor` (accurate enough at tile scale).
+fn to_local(anchor: LatLon, point: LatLon) -> (f64, f64) {
+    let lat_rad = anchor.0.to_radians();
+    let x = (point.1 - anchor.1).to_radians() * lat_rad.cos() * EARTH_RADIUS_M;
+    let y = (point.0 - anchor.0).to_radians() * EARTH_RADIUS_M;
+    (x, y)
+}
+
+fn from_local(anchor: LatLon, local: (f64, f64)) -> LatLon {
+    let lat_rad = anchor.0.to_radians();
+    let lat = anchor.0 + (local.1 / EARTH_RADIUS_M).to_degrees();
+    let lon = anchor.1 + (local.0 / (EARTH_RADIUS_M * lat_rad.cos())).to_degrees();
+    LatLon(lat, lon)
+}
+
+/// Clamped projection of `point` onto segment `a -> b`: `t = clamp(dot(p-a, b-a)/|b-a|^2, 0, 1)`.
+/// Returns the closest point's parameter `t` and the squared distance to it.
+fn project_onto_segment(point: [f64; 2], a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_squared = dx * dx + dy * dy;
+    let t = if length_squared > 0.0 {
+        (((point[0] - a.0) * dx + (point[1] - a.1) * dy) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let closest = (a.0 + t * dx, a.1 + t * dy);
+    let distance_2 = (point[0] - closest.0).powi(2) + (point[1] - closest.1).powi(2);
+    (t, distance_2)
+}
+
+impl GraphReader {
+    /// Finds directed edges near `point`, accessible per `costing`, sorted by distance.
+    ///
+    /// Gathers candidate tiles via [`Self::tiles_in_bbox`] over a small bounding box around
+    /// `point` at every hierarchy level, then searches each tile's cached [`EdgeRTree`] for edges
+    /// within `radius_m`. Both the located edge and, when present, its opposing edge (via
+    /// [`crate::DirectedEdge::opp_index`]) are returned, so callers get both travel directions.
+    pub fn locate(&self, point: LatLon, radius_m: f64, costing: &CostingModel) -> Vec<EdgeLocation> {
+        // Rough meters-per-degree-latitude, good enough to bound the candidate-tile search.
+        let delta_deg = radius_m / 111_000.0;
+        let min = LatLon(point.0 - delta_deg, point.1 - delta_deg);
+        let max = LatLon(point.0 + delta_deg, point.1 + delta_deg);
+
+        let mut results = Vec::new();
+        for level in [GraphLevel::Highway, GraphLevel::Arterial, GraphLevel::Local] {
+            for tile_id in self.tiles_in_bbox(min, max, level) {
+                let Some(tile) = self.graph_tile(tile_id) else {
+                    continue;
+                };
+                self.locate_in_tile(tile_id, &tile, point, radius_m, costing, &mut results);
+            }
+        }
+
+        results.sort_by(|a, b| a.distance_m.total_cmp(&b.distance_m));
+        // Keep only each edge's closest segment hit, which the sort has put first.
+        let mut seen = std::collections::HashSet::new();
+        results.retain(|location| seen.insert(location.edge_id));
+        results
+    }
+
+    fn locate_in_tile(
+        &self,
+        tile_id: GraphId,
+        tile: &GraphTile,
+        point: LatLon,
+        radius_m: f64,
+        costing: &CostingModel,
+        results: &mut Vec<EdgeLocation>,
+    ) {
+        let rtree = {
+            let mut cache = self.edge_rtree_cache.lock().expect("lock poisoned");
+            cache
+                .entry(tile_id)
+                .or_insert_with(|| Arc::new(EdgeRTree::build(tile)))
+                .clone()
+        };
+
+        let local_point = to_local(rtree.anchor, point);
+        for segment in rtree.tree.locate_within_distance([local_point.0, local_point.1], radius_m * radius_m) {
+            let Some(edge) = tile.directededges().get(segment.edge_index as usize) else {
+                continue;
+            };
+            if !costing.edge_accessible(edge) {
+                continue;
+            }
+
+            let (t, distance_2) = project_onto_segment([local_point.0, local_point.1], segment.a, segment.b);
+            let distance_m = distance_2.sqrt();
+            if distance_m > radius_m {
+                continue;
+            }
+
+            let projected_local = (segment.a.0 + t * (segment.b.0 - segment.a.0), segment.a.1 + t * (segment.b.1 - segment.a.1));
+            let distance_along_segment = segment.distance_to_a + t * ((segment.b.0 - segment.a.0).powi(2) + (segment.b.1 - segment.a.1).powi(2)).sqrt();
+            let distance_along_edge = if segment.edge_length > 0.0 {
+                distance_along_segment / segment.edge_length
+            } else {
+                0.0
+            };
+
+            let Some(edge_id) = GraphId::from_parts(tile_id.level(), tile_id.tileid(), segment.edge_index) else {
+                continue;
+            };
+            results.push(EdgeLocation {
+                edge_id,
+                projected_point: from_local(rtree.anchor, projected_local),
+                distance_along_edge,
+                distance_m,
+            });
+
+            if let Some(opposite) = self.opposite_edge_location(tile_id, tile, edge, &results[results.len() - 1]) {
+                results.push(opposite);
+            }
+        }
+    }
+
+    /// Finds the opposing directed edge of `edge` (found via
+    /// [`crate::DirectedEdge::opp_index`]), reusing the just-computed location since both
+    /// directions share the same physical shape.
+    fn opposite_edge_location(
+        &self,
+        tile_id: GraphId,
+        tile: &GraphTile,
+        edge: &crate::DirectedEdge,
+        forward: &EdgeLocation,
+    ) -> Option<EdgeLocation> {
+        let end_node_id = edge.endnode();
+        let end_tile = if edge.leaves_tile() {
+            self.graph_tile(end_node_id)?
+        } else {
+            tile.clone()
+        };
+        let end_node = end_tile.node(end_node_id.id())?;
+        let opp_edges = end_tile.node_edges(end_node);
+        let opp_edge_index = edge.opp_index();
+        opp_edges.get(opp_edge_index as usize)?;
+
+        // `opp_index()` is local to `end_node`'s own outbound edge range, so it needs
+        // `end_node.edge_index()` added back in to become a tile-global directed edge index.
+        let global_edge_index = end_node.edge_index() + opp_edge_index;
+        Some(EdgeLocation {
+            edge_id: GraphId::from_parts(end_node_id.level(), end_node_id.tileid(), global_edge_index)?,
+            projected_point: forward.projected_point,
+            distance_along_edge: 1.0 - forward.distance_along_edge,
+            distance_m: forward.distance_m,
+        })
+    }
+}
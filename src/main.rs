@@ -1,47 +1,170 @@
-use std::{collections::HashMap, env, num::NonZero, time::Instant};
+use std::{
+    collections::HashMap,
+    env,
+    num::NonZero,
+    path::{Path as StdPath, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
+use arc_swap::ArcSwapOption;
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::Html,
+    extract::{Path, Request, State},
+    http::{HeaderMap, StatusCode},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
 use clap::Parser;
 use libvalhalla::{GraphLevel, LatLon};
+use notify::{RecursiveMode, Watcher};
 use serde::Deserialize;
 use serde_json::Value;
 use tokio::{fs::File, io::AsyncReadExt, signal};
-use tracing::info;
+use tracing::{error, info, warn};
+
+mod auth;
+
+use auth::ApiAuth;
 
 #[derive(Parser)]
-struct Config {
+struct Cli {
     /// Port to listen
-    #[arg(long, default_value_t = 3000)]
-    port: u16,
+    #[arg(long)]
+    port: Option<u16>,
     /// Max threads to use
-    #[arg(long, default_value_t = 4)]
-    concurrency: u16,
+    #[arg(long)]
+    concurrency: Option<u16>,
     /// Valhalla base url to send requests to
-    #[arg(long, default_value = "http://localhost:8002")]
-    valhalla_url: String,
+    #[arg(long)]
+    valhalla_url: Option<String>,
     /// Path to valhalla json config file.
     /// Required for an access to valhalla graph information.
     #[arg(long)]
     valhalla_config_path: Option<String>,
+    /// API keys accepted on the `Authorization: Bearer <key>` header. When empty, every request
+    /// is allowed through, matching the previous open-proxy behavior.
+    #[arg(long)]
+    api_key: Vec<String>,
+    /// Maximum number of in-flight requests to the upstream Valhalla instance before new ones are
+    /// shed with `503`.
+    #[arg(long)]
+    max_inflight: Option<usize>,
+    /// Timeout in milliseconds for a single upstream call before it is aborted with `504`.
+    #[arg(long)]
+    upstream_timeout_ms: Option<u64>,
+    /// Number of retries on connection errors to the upstream, with capped exponential backoff.
+    #[arg(long)]
+    retries: Option<u32>,
+    /// Path to the native proxy TOML config file. If omitted, the current directory, the user
+    /// config dir, and `/etc/valhalla-rs/` are searched for `config.toml`.
+    #[arg(long)]
+    config: Option<String>,
+}
+
+/// Fully resolved server configuration, merged from (in priority order) CLI flags, the native
+/// proxy TOML config file, and built-in defaults.
+struct Config {
+    port: u16,
+    concurrency: u16,
+    valhalla_url: String,
+    valhalla_config_path: Option<String>,
+    api_key: Vec<String>,
+    max_inflight: usize,
+    upstream_timeout_ms: u64,
+    retries: u32,
+}
+
+/// Mirrors [`Config`]'s fields, all optional, as carried by the native proxy TOML config file.
+/// `api_key` is not among them: API keys are only taken from the command line.
+#[derive(Deserialize, Default)]
+struct ProxyConfigFile {
+    port: Option<u16>,
+    concurrency: Option<u16>,
+    valhalla_url: Option<String>,
+    valhalla_config_path: Option<String>,
+    max_inflight: Option<usize>,
+    upstream_timeout_ms: Option<u64>,
+    retries: Option<u32>,
+}
+
+impl Config {
+    /// Merges CLI flags over the proxy config file over built-in defaults.
+    fn resolve(cli: Cli) -> Self {
+        let file_path = cli
+            .config
+            .clone()
+            .map(PathBuf::from)
+            .or_else(discover_proxy_config_file);
+        let file = file_path
+            .as_deref()
+            .and_then(load_proxy_config_file)
+            .unwrap_or_default();
+
+        Self {
+            port: cli.port.or(file.port).unwrap_or(3000),
+            concurrency: cli.concurrency.or(file.concurrency).unwrap_or(4),
+            valhalla_url: cli
+                .valhalla_url
+                .or(file.valhalla_url)
+                .unwrap_or_else(|| "http://localhost:8002".to_string()),
+            valhalla_config_path: cli.valhalla_config_path.or(file.valhalla_config_path),
+            api_key: cli.api_key,
+            max_inflight: cli.max_inflight.or(file.max_inflight).unwrap_or(64),
+            upstream_timeout_ms: cli.upstream_timeout_ms.or(file.upstream_timeout_ms).unwrap_or(5000),
+            retries: cli.retries.or(file.retries).unwrap_or(2),
+        }
+    }
+}
+
+/// Searches standard locations for a native proxy config file: the current directory, the user
+/// config dir (e.g. `~/.config/valhalla-rs/config.toml`), then a system-wide directory.
+fn discover_proxy_config_file() -> Option<PathBuf> {
+    let candidates = [
+        Some(PathBuf::from("config.toml")),
+        dirs::config_dir().map(|dir| dir.join("valhalla-rs/config.toml")),
+        Some(PathBuf::from("/etc/valhalla-rs/config.toml")),
+    ];
+    candidates.into_iter().flatten().find(|path| path.exists())
+}
+
+fn load_proxy_config_file(path: &StdPath) -> Option<ProxyConfigFile> {
+    let contents = std::fs::read_to_string(path)
+        .inspect_err(|err| error!("Failed to read proxy config {}: {err}", path.display()))
+        .ok()?;
+    match toml::from_str(&contents) {
+        Ok(config) => {
+            info!("Loaded proxy config from {}", path.display());
+            Some(config)
+        }
+        Err(err) => {
+            error!("Failed to parse proxy config {}: {err}", path.display());
+            None
+        }
+    }
 }
 
 #[derive(Clone)]
 struct AppState {
     http_client: reqwest::Client,
     valhalla_url: String,
-    graph_reader: Option<libvalhalla::GraphReader>,
+    /// Swapped in place whenever the watched tile/traffic extracts change on disk, so in-flight
+    /// requests keep using whatever reader they already `load()`-ed while the swap happens.
+    graph_reader: Arc<ArcSwapOption<libvalhalla::GraphReader>>,
+    /// Path to the Valhalla config file, kept around so `/api/reload` can rebuild the reader.
+    valhalla_config_path: Option<String>,
+    /// Authentication strategy for the proxy endpoints. Boxed so downstream users can plug in
+    /// their own scheme without forking the server.
+    auth: Arc<dyn ApiAuth>,
+    /// Number of retries on connection errors to the upstream, with capped exponential backoff.
+    retries: u32,
 }
 
 fn main() {
     tracing_subscriber::fmt::init();
 
-    let config = Config::parse();
+    let config = Config::resolve(Cli::parse());
 
     tokio::runtime::Builder::new_multi_thread()
         .worker_threads(
@@ -57,18 +180,68 @@ fn main() {
 }
 
 async fn run(config: Config) {
+    let graph_reader = Arc::new(ArcSwapOption::from_pointee_option(
+        config
+            .valhalla_config_path
+            .as_deref()
+            .and_then(load_graph_reader),
+    ));
+
+    if let Some(valhalla_config_path) = config.valhalla_config_path.clone() {
+        spawn_extract_watcher(valhalla_config_path, graph_reader.clone());
+    }
+
+    let auth: Arc<dyn ApiAuth> = if config.api_key.is_empty() {
+        Arc::new(auth::AllowAll)
+    } else {
+        Arc::new(auth::BearerApiKeyAuth::new(config.api_key))
+    };
+
+    let state = AppState {
+        http_client: reqwest::Client::new(),
+        valhalla_url: config.valhalla_url,
+        graph_reader,
+        valhalla_config_path: config.valhalla_config_path,
+        auth,
+        retries: config.retries,
+    };
+
+    // Bounds how many upstream calls can be in flight at once and how long a single one may take,
+    // shedding load with `503`/`504` rather than piling up unbounded requests.
+    let upstream_limits = tower::ServiceBuilder::new()
+        .layer(axum::error_handling::HandleErrorLayer::new(
+            handle_upstream_error,
+        ))
+        .load_shed()
+        .concurrency_limit(config.max_inflight)
+        .timeout(Duration::from_millis(config.upstream_timeout_ms));
+
     // build our application with a route
     let app = Router::new()
         .route("/", get(serve_index_html))
-        .route("/api/request", post(forward_request))
-        .route("/api/traffic/:bbox", get(traffic))
-        .with_state(AppState {
-            http_client: reqwest::Client::new(),
-            valhalla_url: config.valhalla_url,
-            graph_reader: config
-                .valhalla_config_path
-                .map(|path| libvalhalla::GraphReader::new(path.into())),
-        });
+        .route(
+            "/api/request",
+            post(forward_request)
+                .layer(upstream_limits.clone())
+                .layer(middleware::from_fn_with_state(state.clone(), require_auth)),
+        )
+        .route(
+            "/api/traffic/:bbox",
+            get(traffic)
+                .layer(upstream_limits.clone())
+                .layer(middleware::from_fn_with_state(state.clone(), require_auth)),
+        )
+        .route(
+            "/api/traffic/:z/:x/:y.mvt",
+            get(traffic_mvt)
+                .layer(upstream_limits)
+                .layer(middleware::from_fn_with_state(state.clone(), require_auth)),
+        )
+        .route(
+            "/api/reload",
+            post(reload).layer(middleware::from_fn_with_state(state.clone(), require_auth)),
+        )
+        .with_state(state);
 
     let listener = tokio::net::TcpListener::bind(("0.0.0.0", config.port))
         .await
@@ -94,6 +267,160 @@ async fn run(config: Config) {
         .unwrap();
 }
 
+/// Maps `tower`'s load-shedding/timeout errors to the right `StatusCode`, since neither
+/// `LoadShedLayer` nor `TimeoutLayer` produce an `axum` response on their own.
+async fn handle_upstream_error(err: tower::BoxError) -> (StatusCode, String) {
+    if err.is::<tower::load_shed::error::Overloaded>() {
+        (StatusCode::SERVICE_UNAVAILABLE, "Too many in-flight requests".to_string())
+    } else if err.is::<tower::timeout::error::Elapsed>() {
+        (StatusCode::GATEWAY_TIMEOUT, "Upstream request timed out".to_string())
+    } else {
+        (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+    }
+}
+
+/// Sends `request`, retrying on connection errors with capped exponential backoff
+/// (100ms, 200ms, 400ms, ..., at most 2s) up to `retries` times. Only meant for idempotent calls.
+async fn send_with_retries(
+    request: reqwest::RequestBuilder,
+    retries: u32,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let mut backoff = Duration::from_millis(100);
+    let mut attempt = 0;
+    loop {
+        let Some(next_request) = request.try_clone() else {
+            // Body isn't cloneable (e.g. a stream); just send it once.
+            return request.send().await;
+        };
+        match next_request.send().await {
+            Ok(response) => return Ok(response),
+            Err(err) if attempt < retries && err.is_connect() => {
+                attempt += 1;
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(Duration::from_secs(2));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Rejects the request with `401` unless `state.auth` accepts its headers.
+async fn require_auth(State(state): State<AppState>, headers: HeaderMap, request: Request, next: Next) -> Response {
+    match state.auth.authenticate(&headers) {
+        Ok(_principal) => next.run(request).await,
+        Err(err) => (StatusCode::UNAUTHORIZED, err.to_string()).into_response(),
+    }
+}
+
+/// Builds a fresh `GraphReader` from the given Valhalla config, logging (rather than panicking)
+/// on failure so a bad extract at startup or reload time doesn't take the whole process down.
+fn load_graph_reader(valhalla_config_path: &str) -> Option<libvalhalla::GraphReader> {
+    let config = match libvalhalla::Config::from_file(valhalla_config_path) {
+        Ok(config) => config,
+        Err(err) => {
+            error!("{err}");
+            return None;
+        }
+    };
+    match libvalhalla::GraphReader::new(&config) {
+        Ok(reader) => Some(reader),
+        Err(err) => {
+            error!("{err}");
+            None
+        }
+    }
+}
+
+/// Pulls `mjolnir.tile_extract`/`mjolnir.traffic_extract` out of the raw Valhalla config JSON so we
+/// know which files to watch. Returns only the ones present, since either may be omitted.
+fn extract_paths(valhalla_config_path: &str) -> Vec<PathBuf> {
+    let Ok(contents) = std::fs::read_to_string(valhalla_config_path) else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<Value>(&contents) else {
+        return Vec::new();
+    };
+    ["tile_extract", "traffic_extract"]
+        .into_iter()
+        .filter_map(|key| json.get("mjolnir")?.get(key)?.as_str())
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Watches the tile/traffic extracts referenced by the Valhalla config and rebuilds the shared
+/// `GraphReader` on every modify/rename event, storing the fresh reader into the `ArcSwapOption`.
+/// In-flight requests keep using whatever reader they already loaded.
+fn spawn_extract_watcher(
+    valhalla_config_path: String,
+    graph_reader: Arc<ArcSwapOption<libvalhalla::GraphReader>>,
+) {
+    let paths = extract_paths(&valhalla_config_path);
+    if paths.is_empty() {
+        warn!("No tile/traffic extract paths found in {valhalla_config_path}, hot-reload disabled");
+        return;
+    }
+
+    std::thread::spawn(move || {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                error!("Failed to create extract watcher: {err}");
+                return;
+            }
+        };
+        for path in &paths {
+            let watch_target = path.parent().unwrap_or(StdPath::new("."));
+            if let Err(err) = watcher.watch(watch_target, RecursiveMode::NonRecursive) {
+                error!("Failed to watch {}: {err}", watch_target.display());
+            }
+        }
+
+        for event in &rx {
+            let Ok(event) = event else { continue };
+            if !matches!(
+                event.kind,
+                notify::EventKind::Modify(_) | notify::EventKind::Create(_)
+            ) {
+                continue;
+            }
+            // Coalesce bursts of filesystem events (e.g. a temp-file-then-rename write) into a
+            // single reload instead of rebuilding the reader once per event.
+            std::thread::sleep(Duration::from_millis(200));
+            while rx.try_recv().is_ok() {}
+            reload_graph_reader(&valhalla_config_path, &graph_reader);
+        }
+    });
+}
+
+/// Rebuilds the `GraphReader` and stores it, keeping the previous reader in place on failure
+/// instead of dropping to `None`.
+fn reload_graph_reader(
+    valhalla_config_path: &str,
+    graph_reader: &ArcSwapOption<libvalhalla::GraphReader>,
+) {
+    match load_graph_reader(valhalla_config_path) {
+        Some(reader) => {
+            info!("Reloaded graph reader from {valhalla_config_path}");
+            graph_reader.store(Some(Arc::new(reader)));
+        }
+        None => error!("Keeping previous graph reader: reload from {valhalla_config_path} failed"),
+    }
+}
+
+/// Forces an immediate reload of the tile/traffic extracts, rather than waiting for the
+/// filesystem watcher to notice a change.
+async fn reload(State(state): State<AppState>) -> Result<StatusCode, (StatusCode, String)> {
+    let Some(valhalla_config_path) = &state.valhalla_config_path else {
+        return Err((
+            StatusCode::IM_A_TEAPOT,
+            "Traffic information was not enabled".to_string(),
+        ));
+    };
+    reload_graph_reader(valhalla_config_path, &state.graph_reader);
+    Ok(StatusCode::NO_CONTENT)
+}
+
 async fn serve_index_html() -> Result<Html<String>, (StatusCode, String)> {
     let index_html = "web/index.html";
     let Ok(mut file) = File::open(index_html).await else {
@@ -130,11 +457,11 @@ async fn forward_request(
     Json(request): Json<RequestToForward>,
 ) -> Result<Json<Value>, (StatusCode, String)> {
     let begin = Instant::now();
-    let response = state
+    let request_builder = state
         .http_client
         .post(format!("{}/{}", state.valhalla_url, request.endpoint))
-        .json(&request.payload)
-        .send()
+        .json(&request.payload);
+    let response = send_with_retries(request_builder, state.retries)
         .await
         .map_err(|err| (StatusCode::BAD_REQUEST, err.to_string()))?;
     info!(
@@ -161,7 +488,8 @@ async fn traffic(
         ));
     };
 
-    let Some(reader) = &state.graph_reader else {
+    let reader = state.graph_reader.load();
+    let Some(reader) = reader.as_deref() else {
         return Err((
             StatusCode::IM_A_TEAPOT,
             "Traffic information was not enabled".to_string(),
@@ -196,6 +524,88 @@ async fn traffic(
     Ok(Json(serde_json::to_value(edges).unwrap()))
 }
 
+/// Serves live traffic as a Mapbox Vector Tile keyed by z/x/y, so the web client can add it as a
+/// native vector source/layer instead of re-decoding a shape->speed JSON map on every frame.
+async fn traffic_mvt(
+    State(state): State<AppState>,
+    Path((z, x, y)): Path<(u8, u32, u32)>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let reader = state.graph_reader.load();
+    let Some(reader) = reader.as_deref() else {
+        return Err((
+            StatusCode::IM_A_TEAPOT,
+            "Traffic information was not enabled".to_string(),
+        ));
+    };
+
+    let (min, max) = tile_bbox(z, x, y);
+    let begin = Instant::now();
+    let edges: Vec<_> = [GraphLevel::Highway, GraphLevel::Arterial, GraphLevel::Local]
+        .into_iter()
+        .flat_map(|level| reader.tiles_in_bbox(min, max, level))
+        .flat_map(|tile_id| reader.get_tile_traffic_flows(tile_id))
+        .collect();
+
+    let tile = encode_traffic_tile(z, x, y, &edges).map_err(|err| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to encode traffic tile: {err}"),
+        )
+    })?;
+    info!(
+        "Encoded {z}/{x}/{y}.mvt with {} edges in {}ms",
+        edges.len(),
+        begin.elapsed().as_millis()
+    );
+
+    Ok((
+        [(axum::http::header::CONTENT_TYPE, "application/vnd.mapbox-vector-tile")],
+        tile,
+    ))
+}
+
+/// Lat/lon bounds of a slippy-map tile, per the standard Web Mercator tiling scheme.
+fn tile_bbox(z: u8, x: u32, y: u32) -> (LatLon, LatLon) {
+    fn lat_lon(z: u8, x: f64, y: f64) -> LatLon {
+        let n = 2f64.powi(z as i32);
+        let lon = x / n * 360.0 - 180.0;
+        let lat_rad = (std::f64::consts::PI * (1.0 - 2.0 * y / n)).sinh().atan();
+        LatLon(lat_rad.to_degrees() as f32, lon as f32)
+    }
+    // Note the flip: tile y grows southward, so the northwest corner uses the smaller y.
+    (lat_lon(z, x as f64, (y + 1) as f64), lat_lon(z, (x + 1) as f64, y as f64))
+}
+
+/// Packs traffic edges into a single-layer Mapbox Vector Tile, reprojecting each edge's
+/// polyline6 shape into tile-local pixel coordinates and quantizing the jam factor into the same
+/// 0..=10 speed classes the JSON endpoint uses.
+fn encode_traffic_tile(z: u8, x: u32, y: u32, edges: &[libvalhalla::TrafficEdge]) -> mvt::Result<Vec<u8>> {
+    const EXTENT: u32 = 4096;
+
+    let mut tile = mvt::Tile::new(EXTENT);
+    let mut layer = tile.create_layer("traffic");
+    let transform = mvt::Transform::new(z as u32, x, y);
+
+    for edge in edges {
+        let points = edge.decoded_shape();
+        if points.len() < 2 {
+            continue;
+        }
+        let mut encoder = mvt::GeomEncoder::new(mvt::GeomType::Linestring, transform);
+        for point in &points {
+            encoder.add_point(point.1 as f64, point.0 as f64)?;
+        }
+        let speed_class = 10 - (edge.jam_factor * 10.0).round() as i32;
+
+        let mut feature = layer.into_feature(encoder.encode()?);
+        feature.add_tag_sint("speed_class", speed_class as i64)?;
+        layer = feature.into_layer();
+    }
+
+    tile.add_layer(layer)?;
+    tile.to_bytes()
+}
+
 fn parse_coordinate(coord: &str) -> Option<LatLon> {
     let (lat, lon) = coord.split_once(',')?;
     let lat = lat.parse::<f32>().ok()?;
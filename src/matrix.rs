@@ -0,0 +1,165 @@
+//! Many-to-many time/distance matrix, built on the same bounded label-setting expansion
+//! `reachability.rs` and `route.rs` use: one Dijkstra per source, early-terminated once every
+//! target has settled rather than running to exhaustion.
+//!
+//! This is a scoped-down take on Valhalla's `thor/costmatrix.cc`: that implementation runs
+//! simultaneous forward expansions from every source and reverse expansions from every target,
+//! meeting in the middle on shared edges. What's implemented here is a plain unidirectional
+//! one-to-many Dijkstra run once per source instead — simpler, but for a sizeable
+//! `sources x targets` it re-explores overlapping territory the bidirectional search wouldn't, so
+//! it's more expensive per call. [`GraphReader::cost_matrix`]'s `max_cost_secs` threshold bounds
+//! that cost the same way the real implementation's threshold does, just without the
+//! meet-in-the-middle savings.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::{CostingModel, GraphId, GraphReader, LatLon};
+
+/// Radius, in meters, `GraphReader::cost_matrix` snaps source/target points to the road network
+/// within, via [`GraphReader::locate`].
+const SNAP_RADIUS_M: f64 = 50.0;
+
+/// One source/target cell of a [`GraphReader::cost_matrix`] result.
+#[derive(Debug, Clone, Copy)]
+pub struct MatrixCell {
+    /// Accumulated travel time, in seconds.
+    pub time_secs: f64,
+    /// Accumulated travel distance, in meters.
+    pub distance_m: f64,
+}
+
+/// Min-heap entry for the per-source Dijkstra expansion, ordered by ascending accumulated time.
+struct Frontier {
+    time_secs: f64,
+    node: GraphId,
+}
+
+impl PartialEq for Frontier {
+    fn eq(&self, other: &Self) -> bool {
+        self.time_secs == other.time_secs
+    }
+}
+impl Eq for Frontier {}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so `BinaryHeap` (a max-heap) acts as a min-heap on `time_secs`.
+        other.time_secs.total_cmp(&self.time_secs)
+    }
+}
+
+impl GraphReader {
+    /// Computes a `sources.len() x targets.len()` time/distance matrix, accessible per `costing`.
+    ///
+    /// Each point is first snapped to its nearest accessible edge (via [`Self::locate`], within
+    /// [`SNAP_RADIUS_M`]) and resolved to that edge's begin node; a source with no edge nearby
+    /// leaves its entire row `None`, and a target with no edge nearby its entire column. One Dijkstra expansion then runs per source, relaxing edges
+    /// the same way [`Self::reachable`] does (via [`CostingModel::edge_accessible`] and
+    /// [`crate::GraphTile::edge_closed`]), terminating early once every target for that row has
+    /// settled, or as soon as the frontier's cost exceeds `max_cost_secs` (`None` for no bound). A
+    /// target left unreached within that bound (no accessible path, or one costlier than the
+    /// threshold) is `None`.
+    pub fn cost_matrix(
+        &self,
+        sources: &[LatLon],
+        targets: &[LatLon],
+        costing: &CostingModel,
+        max_cost_secs: Option<f64>,
+    ) -> Vec<Vec<Option<MatrixCell>>> {
+        let target_nodes: Vec<Option<GraphId>> = targets.iter().map(|&point| self.nearest_node(point, costing)).collect();
+
+        sources
+            .iter()
+            .map(|&source| {
+                let Some(source_node) = self.nearest_node(source, costing) else {
+                    return vec![None; targets.len()];
+                };
+                let reached = self.one_to_many(source_node, &target_nodes, costing, max_cost_secs);
+                target_nodes.iter().map(|node| node.and_then(|node| reached.get(&node).copied())).collect()
+            })
+            .collect()
+    }
+
+    /// Resolves `point` to the begin node of its nearest accessible edge within [`SNAP_RADIUS_M`].
+    fn nearest_node(&self, point: LatLon, costing: &CostingModel) -> Option<GraphId> {
+        let location = self.locate(point, SNAP_RADIUS_M, costing).into_iter().next()?;
+        let tile = self.graph_tile(location.edge_id.tile())?;
+        let node_index = crate::shortcut::node_owning_edge(&tile, location.edge_id.id())?;
+        GraphId::from_parts(location.edge_id.level(), location.edge_id.tileid(), node_index)
+    }
+
+    /// Dijkstra expansion from `origin`, stopping once every `Some` entry of `targets` has settled
+    /// or the frontier's cost exceeds `max_cost_secs` (`None` for no bound).
+    fn one_to_many(
+        &self,
+        origin: GraphId,
+        targets: &[Option<GraphId>],
+        costing: &CostingModel,
+        max_cost_secs: Option<f64>,
+    ) -> HashMap<GraphId, MatrixCell> {
+        let mut remaining: HashSet<GraphId> = targets.iter().filter_map(|&node| node).filter(|&node| node != origin).collect();
+
+        let mut best = HashMap::new();
+        best.insert(origin, MatrixCell { time_secs: 0.0, distance_m: 0.0 });
+
+        let mut open = BinaryHeap::new();
+        open.push(Frontier { time_secs: 0.0, node: origin });
+
+        while let Some(Frontier { time_secs, node }) = open.pop() {
+            if remaining.is_empty() || time_secs > max_cost_secs.unwrap_or(f64::INFINITY) {
+                break;
+            }
+            if time_secs > best.get(&node).map(|cell| cell.time_secs).unwrap_or(f64::INFINITY) {
+                continue; // stale heap entry
+            }
+            remaining.remove(&node);
+
+            let Some(tile) = self.graph_tile(node.tile()) else {
+                continue;
+            };
+            let Some(node_info) = tile.node(node.id()) else {
+                continue;
+            };
+            if !costing.node_accessible(node_info) {
+                continue;
+            }
+            let distance_m = best[&node].distance_m;
+
+            // Hop to the same physical node on other hierarchy levels at no extra cost, matching
+            // `GraphReader::reachable`, so long-range pairs aren't stuck on local-level edges.
+            for transition in tile.node_transitions(node_info).iter() {
+                let next_node = transition.endnode();
+                if time_secs < best.get(&next_node).map(|cell| cell.time_secs).unwrap_or(f64::INFINITY) {
+                    best.insert(next_node, MatrixCell { time_secs, distance_m });
+                    open.push(Frontier { time_secs, node: next_node });
+                }
+            }
+
+            for edge in tile.node_edges(node_info) {
+                if !costing.edge_accessible(edge) || tile.edge_closed(edge) {
+                    continue;
+                }
+                let next_node = edge.endnode();
+                let next_time_secs = time_secs + costing.edge_cost(edge, &tile, 0).secs as f64;
+                if next_time_secs < best.get(&next_node).map(|cell| cell.time_secs).unwrap_or(f64::INFINITY) {
+                    best.insert(
+                        next_node,
+                        MatrixCell { time_secs: next_time_secs, distance_m: distance_m + edge.length() as f64 },
+                    );
+                    open.push(Frontier { time_secs: next_time_secs, node: next_node });
+                }
+            }
+        }
+
+        // Drop tentative costs the expansion stopped before settling: they lie above the bound.
+        best.retain(|_, cell| cell.time_secs <= max_cost_secs.unwrap_or(f64::INFINITY));
+        best
+    }
+}
@@ -0,0 +1,248 @@
+//! A long-lived navigation session that turns a one-shot [`Actor::route`] response into something
+//! that can track live GPS fixes and decide when to re-route, without crossing the FFI boundary
+//! on every fix.
+
+use crate::{Actor, Error, LatLon, actor::Response, geometry, proto};
+
+/// Number of consecutive off-route fixes required before [`NavigationSession::update`] triggers a
+/// re-route, to avoid flapping on a single noisy GPS fix.
+const OFF_ROUTE_FIXES_TO_REROUTE: u32 = 3;
+
+/// Outcome of feeding a GPS fix into a [`NavigationSession`].
+#[derive(Debug)]
+pub enum NavigationState {
+    /// The fix hasn't triggered a re-route (yet).
+    OnRoute {
+        /// Remaining distance along the route geometry to the destination, in meters.
+        remaining_distance: f64,
+        /// Index into the active route's maneuvers the traveler is currently working towards.
+        next_maneuver_index: usize,
+        /// Whether this fix was farther than the off-route threshold from the route geometry.
+        /// Set before [`OFF_ROUTE_FIXES_TO_REROUTE`] consecutive off-route fixes accumulate into an
+        /// actual re-route, so callers can surface "off route" in the UI immediately rather than
+        /// waiting for the threshold to trip.
+        off_route: bool,
+    },
+    /// The fix was off-route for [`OFF_ROUTE_FIXES_TO_REROUTE`] consecutive updates, so the
+    /// session requested and now holds a fresh route from the current position to the destination.
+    Rerouted(Response),
+}
+
+/// Stateful wrapper around [`Actor`] for turn-by-turn navigation: holds the active route geometry
+/// and destination, snaps incoming GPS fixes to it, and re-routes automatically when the traveler
+/// strays too far from the planned path.
+pub struct NavigationSession {
+    destination: LatLon,
+    costing_type: i32,
+    off_route_threshold_m: f64,
+    /// Decoded shape of the active route's first leg.
+    shape: Vec<LatLon>,
+    /// Shape index at which each maneuver begins, parallel to the route's maneuver list.
+    maneuver_shape_indices: Vec<usize>,
+    consecutive_off_route: u32,
+    /// Shape index of the furthest-along segment a fix has ever snapped to. Monotonically
+    /// non-decreasing: [`nearest_point_on_route`] only searches `shape[traveled_index..]`, so a fix
+    /// that happens to land nearer an earlier point on a route looping back near itself (common on
+    /// service roads/ramps) can't snap the traveler backwards.
+    traveled_index: usize,
+}
+
+impl NavigationSession {
+    /// Starts a new session from an initial [`Actor::route`] response.
+    pub fn new(response: &Response, destination: LatLon, off_route_threshold_m: f64) -> Result<Self, Error> {
+        let (shape, maneuver_shape_indices, costing_type) = decode_route(response)?;
+        Ok(Self {
+            destination,
+            costing_type,
+            off_route_threshold_m,
+            shape,
+            maneuver_shape_indices,
+            consecutive_off_route: 0,
+            traveled_index: 0,
+        })
+    }
+
+    /// Feeds a new GPS fix into the session, snapping it to the active route geometry.
+    ///
+    /// Crosses the FFI boundary (via `actor`) only when the fix has been off-route for
+    /// [`OFF_ROUTE_FIXES_TO_REROUTE`] consecutive updates.
+    pub fn update(&mut self, actor: &mut Actor, fix: LatLon) -> NavigationState {
+        let Some((closest_segment, perpendicular_distance_m)) = nearest_point_on_route(&self.shape, self.traveled_index, fix)
+        else {
+            return NavigationState::OnRoute {
+                remaining_distance: 0.0,
+                next_maneuver_index: 0,
+                off_route: false,
+            };
+        };
+        self.traveled_index = closest_segment;
+
+        let off_route = perpendicular_distance_m > self.off_route_threshold_m;
+        if off_route {
+            self.consecutive_off_route += 1;
+        } else {
+            self.consecutive_off_route = 0;
+        }
+
+        if self.consecutive_off_route < OFF_ROUTE_FIXES_TO_REROUTE {
+            let remaining_distance = remaining_distance_m(&self.shape, closest_segment, fix);
+            let next_maneuver_index = self
+                .maneuver_shape_indices
+                .iter()
+                .position(|&begin| begin > closest_segment)
+                .unwrap_or(self.maneuver_shape_indices.len().saturating_sub(1));
+            return NavigationState::OnRoute {
+                remaining_distance,
+                next_maneuver_index,
+                off_route,
+            };
+        }
+
+        let request = proto::Options {
+            costing_type: self.costing_type,
+            locations: vec![
+                proto::Location {
+                    ll: fix.into(),
+                    ..Default::default()
+                },
+                proto::Location {
+                    ll: self.destination.into(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        match actor.route(&request) {
+            Ok(response) => {
+                if let Ok((shape, maneuver_shape_indices, _)) = decode_route(&response) {
+                    self.shape = shape;
+                    self.maneuver_shape_indices = maneuver_shape_indices;
+                    self.consecutive_off_route = 0;
+                    self.traveled_index = 0;
+                }
+                NavigationState::Rerouted(response)
+            }
+            Err(_) => NavigationState::OnRoute {
+                remaining_distance: remaining_distance_m(&self.shape, closest_segment, fix),
+                next_maneuver_index: 0,
+                off_route: true,
+            },
+        }
+    }
+}
+
+/// Extracts the decoded shape, maneuver shape-index boundaries, and costing of a route response's
+/// first leg. Only [`Response::Pbf`] carries the structured trip data this session needs.
+fn decode_route(response: &Response) -> Result<(Vec<LatLon>, Vec<usize>, i32), Error> {
+    let Response::Pbf(api) = response else {
+        return Err(Error("NavigationSession requires a PBF route response".into()));
+    };
+    let leg = api
+        .trip
+        .as_ref()
+        .and_then(|trip| trip.legs.first())
+        .ok_or_else(|| Error("Route response has no legs".into()))?;
+
+    let shape = geometry::decode_polyline(&leg.shape, 6);
+    let maneuver_shape_indices = leg
+        .maneuvers
+        .iter()
+        .map(|maneuver| maneuver.begin_shape_index as usize)
+        .collect();
+    let costing_type = api.options.as_ref().map_or(0, |options| options.costing_type);
+    Ok((shape, maneuver_shape_indices, costing_type))
+}
+
+/// Projects `fix` onto the polyline `shape`, searching only the remaining portion of the route
+/// from `start_index` onwards so a fix can't snap to an earlier point the traveler has already
+/// passed (e.g. where a route loops back near itself, on service roads/ramps). Returns the index
+/// of the closest segment's start point and the perpendicular distance to it in meters, using a
+/// local equirectangular projection (accurate enough at the scale of a single route).
+fn nearest_point_on_route(shape: &[LatLon], start_index: usize, fix: LatLon) -> Option<(usize, f64)> {
+    let search_space = shape.get(start_index..)?;
+    if search_space.len() < 2 {
+        return None;
+    }
+
+    let origin = shape[0];
+    let to_local = |point: LatLon| -> (f64, f64) {
+        let lat_rad = origin.0.to_radians();
+        let x = (point.1 - origin.1).to_radians() * lat_rad.cos() * geometry::EARTH_RADIUS_M;
+        let y = (point.0 - origin.0).to_radians() * geometry::EARTH_RADIUS_M;
+        (x, y)
+    };
+
+    let fix_local = to_local(fix);
+    let mut best: Option<(usize, f64)> = None;
+
+    for (index, pair) in search_space.windows(2).enumerate() {
+        let a = to_local(pair[0]);
+        let b = to_local(pair[1]);
+        let distance = distance_to_segment(fix_local, a, b);
+        let is_closer = match best {
+            Some((_, best_distance)) => distance < best_distance,
+            None => true,
+        };
+        if is_closer {
+            best = Some((start_index + index, distance));
+        }
+    }
+    best
+}
+
+fn distance_to_segment(point: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_squared = dx * dx + dy * dy;
+    let t = if length_squared > 0.0 {
+        (((point.0 - a.0) * dx + (point.1 - a.1) * dy) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let closest = (a.0 + t * dx, a.1 + t * dy);
+    ((point.0 - closest.0).powi(2) + (point.1 - closest.1).powi(2)).sqrt()
+}
+
+/// Remaining route distance from `fix`'s closest segment to the end of `shape`.
+fn remaining_distance_m(shape: &[LatLon], from_segment: usize, fix: LatLon) -> f64 {
+    let mut distance = geometry::haversine_distance_m(fix, shape[from_segment + 1]);
+    for pair in shape[from_segment + 1..].windows(2) {
+        distance += geometry::haversine_distance_m(pair[0], pair[1]);
+    }
+    distance
+}
+
+/// Resegments `shape` into points spaced `spacing_m` apart along its length, via haversine-distance
+/// linear interpolation between consecutive shape points. The original last point is always kept,
+/// even if it falls short of a full `spacing_m` step, so the resampled shape still ends exactly
+/// where `shape` does. Useful for feeding a uniform cadence of "distance traveled" checkpoints to
+/// code that otherwise has to reckon with a route encoder's uneven shape-generalization spacing.
+pub fn resample_shape(shape: &[LatLon], spacing_m: f64) -> Vec<LatLon> {
+    if shape.len() < 2 || spacing_m <= 0.0 {
+        return shape.to_vec();
+    }
+
+    let mut result = vec![shape[0]];
+    let mut carry = 0.0; // distance already covered past the last emitted point
+
+    for pair in shape.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let segment_length = geometry::haversine_distance_m(a, b);
+        if segment_length <= 0.0 {
+            continue;
+        }
+
+        let mut distance = spacing_m - carry;
+        while distance < segment_length {
+            let t = distance / segment_length;
+            result.push(LatLon(a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t));
+            distance += spacing_m;
+        }
+        carry = distance - segment_length;
+    }
+
+    if result.last() != shape.last() {
+        result.push(*shape.last().unwrap());
+    }
+    result
+}
@@ -0,0 +1,189 @@
+//! Isochrone / reachability support: promoting the `furthest_node_distance` closure the
+//! `costing_model` test hand-rolls into a first-class Dijkstra expansion bounded by a time or
+//! distance budget.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::{CostingModel, GraphId, GraphReader};
+
+/// Limit on how far [`GraphReader::reachable`] is allowed to expand from the origin.
+#[derive(Debug, Clone, Copy)]
+pub enum CostBudget {
+    /// Cap on accumulated edge length, in meters.
+    Distance(f64),
+    /// Cap on accumulated travel time, in seconds.
+    Time(f64),
+}
+
+/// A directed edge touched by a [`GraphReader::reachable`] expansion.
+#[derive(Debug, Clone, Copy)]
+pub struct ReachableEdge {
+    /// `GraphId` of the directed edge.
+    pub edge_id: GraphId,
+    /// Fraction of the edge that fits within the budget, from `0.0` (exclusive) to `1.0`. Edges
+    /// fully within budget are `1.0`; the edges straddling the budget boundary are less, which is
+    /// enough to clip an isoline polygon to the edge's shape.
+    pub fraction: f64,
+}
+
+/// Result of [`GraphReader::reachable`]: every node reached within budget, and the edges crossed
+/// to reach them.
+#[derive(Debug, Clone, Default)]
+pub struct Reachability {
+    /// Minimum accumulated cost to reach each node, in the same unit as the budget.
+    pub costs: HashMap<GraphId, f64>,
+    /// Edges touched during the expansion, including the partially-traversed boundary edges.
+    pub edges: Vec<ReachableEdge>,
+}
+
+/// Min-heap entry for the Dijkstra expansion, ordered by ascending accumulated cost.
+struct Frontier {
+    cost: f64,
+    node: GraphId,
+}
+
+impl PartialEq for Frontier {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+impl Eq for Frontier {}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so `BinaryHeap` (a max-heap) acts as a min-heap on `cost`.
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+impl GraphReader {
+    /// Finds every node reachable from `origin` within `budget`, accessible per `costing`.
+    ///
+    /// Runs a Dijkstra expansion seeded at `origin`, relaxing outgoing edges via
+    /// [`CostingModel::edge_accessible`]/[`CostingModel::node_accessible`], skipping edges
+    /// [`crate::GraphTile::edge_closed`] reports as closed, and pruning any edge whose cumulative
+    /// cost would exceed `budget`; the edge that first crosses the boundary is still recorded,
+    /// with [`ReachableEdge::fraction`] set to how much of it fits, so isoline polygons can clip to
+    /// the edge's shape rather than stopping at its start node. Also follows
+    /// [`crate::NodeTransition`]s to other hierarchy levels at zero extra cost, so a large-budget
+    /// expansion continues on arterial/highway edges instead of walking every local-level edge.
+    ///
+    /// When `parallel` is set, nodes that settle at the same cost (there are often many with a
+    /// distance budget, since edge lengths are whole meters) are relaxed concurrently across a rayon
+    /// thread pool, grouped by tile so tile fetches within a batch aren't contended.
+    pub fn reachable(&self, origin: GraphId, budget: CostBudget, costing: &CostingModel, parallel: bool) -> Reachability {
+        let mut costs = HashMap::new();
+        costs.insert(origin, 0.0);
+        let mut result = Reachability { costs, edges: Vec::new() };
+
+        let mut open = BinaryHeap::new();
+        open.push(Frontier { cost: 0.0, node: origin });
+
+        while let Some(Frontier { cost, node }) = open.pop() {
+            if cost > *result.costs.get(&node).unwrap_or(&f64::INFINITY) {
+                continue; // stale heap entry
+            }
+
+            // Drain every other entry tied at `cost` into the same batch: Dijkstra's correctness
+            // only relies on nodes being settled in non-decreasing cost order, so nodes tied at
+            // the same cost can be relaxed in any order (or in parallel) relative to each other.
+            let mut batch = vec![node];
+            while let Some(next) = open.peek() {
+                if next.cost > cost {
+                    break;
+                }
+                batch.push(open.pop().unwrap().node);
+            }
+            batch.retain(|&node| cost <= *result.costs.get(&node).unwrap_or(&f64::INFINITY));
+
+            let relax_node = |node: GraphId| -> Vec<(GraphId, f64, Option<ReachableEdge>)> {
+                let Some(tile) = self.graph_tile(node.tile()) else {
+                    return Vec::new();
+                };
+                let Some(node_info) = tile.node(node.id()) else {
+                    return Vec::new();
+                };
+                if !costing.node_accessible(node_info) {
+                    return Vec::new();
+                }
+
+                // Hop to the same physical node on other hierarchy levels at no extra cost, so the
+                // expansion can continue on arterial/highway edges for long-range budgets instead of
+                // being stuck walking every local-level edge.
+                let transitions = tile
+                    .node_transitions(node_info)
+                    .iter()
+                    .map(|transition| (transition.endnode(), cost, None::<ReachableEdge>));
+
+                let begin_index = node_info.edge_index();
+                let edges = tile.node_edges(node_info).iter().enumerate().filter_map(|(offset, edge)| {
+                    if !costing.edge_accessible(edge) || tile.edge_closed(edge) {
+                        return None;
+                    }
+                    let edge_index = begin_index + offset as u32;
+                    let edge_id = GraphId::from_parts(node.tile().level(), node.tile().tileid(), edge_index)?;
+                    let edge_cost = edge_cost(&tile, edge, costing, budget);
+                    let next_cost = cost + edge_cost;
+
+                    match budget {
+                        CostBudget::Distance(max) | CostBudget::Time(max) => {
+                            if next_cost <= max {
+                                Some((
+                                    edge.endnode(),
+                                    next_cost,
+                                    Some(ReachableEdge { edge_id, fraction: 1.0 }),
+                                ))
+                            } else if cost < max {
+                                // Boundary edge: only part of it fits within budget, so its end node
+                                // isn't reached and gets no cost (an infinite one is never recorded).
+                                let fraction = ((max - cost) / edge_cost).clamp(0.0, 1.0);
+                                Some((edge.endnode(), f64::INFINITY, Some(ReachableEdge { edge_id, fraction })))
+                            } else {
+                                None
+                            }
+                        }
+                    }
+                });
+
+                transitions.chain(edges).collect()
+            };
+
+            let relaxed: Vec<_> = if parallel {
+                use rayon::prelude::*;
+                batch.into_par_iter().flat_map_iter(relax_node).collect()
+            } else {
+                batch.into_iter().flat_map(relax_node).collect()
+            };
+
+            for (next_node, next_cost, reachable_edge) in relaxed {
+                if let Some(reachable_edge) = reachable_edge {
+                    result.edges.push(reachable_edge);
+                }
+                if next_cost < *result.costs.get(&next_node).unwrap_or(&f64::INFINITY) {
+                    result.costs.insert(next_node, next_cost);
+                    open.push(Frontier { cost: next_cost, node: next_node });
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Cost of traversing `edge` in the unit `budget` is expressed in: meters for
+/// [`CostBudget::Distance`], seconds of travel time (via [`CostingModel::edge_cost`], so
+/// time-dependent speeds and soft-restriction penalties are honored the same way they would be
+/// during an actual route search) for [`CostBudget::Time`].
+fn edge_cost(tile: &crate::GraphTile, edge: &crate::DirectedEdge, costing: &CostingModel, budget: CostBudget) -> f64 {
+    match budget {
+        CostBudget::Distance(_) => edge.length() as f64,
+        CostBudget::Time(_) => costing.edge_cost(edge, tile, 0).secs as f64,
+    }
+}
@@ -0,0 +1,354 @@
+//! Strongly-typed views over an [`Actor`](crate::Actor) endpoint's [`Response`], for callers that
+//! want a [`RouteResult`]/[`MatrixResult`]/[`IsochroneContour`] list instead of walking
+//! `proto::Api` or a raw JSON string themselves. Works the same whether the underlying `Response`
+//! is [`Response::Pbf`] or [`Response::Json`], where Valhalla's API supports both.
+
+use crate::{Error, LatLon, actor::Response, geometry};
+
+/// Kind of maneuver a [`Maneuver`] represents, numbered the same way as Valhalla's
+/// `TripLeg_Maneuver_Type`/JSON `type` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManeuverKind {
+    Start,
+    StartRight,
+    StartLeft,
+    Destination,
+    DestinationRight,
+    DestinationLeft,
+    Becomes,
+    Continue,
+    SlightRight,
+    Right,
+    SharpRight,
+    UturnRight,
+    UturnLeft,
+    SharpLeft,
+    Left,
+    SlightLeft,
+    RampStraight,
+    RampRight,
+    RampLeft,
+    ExitRight,
+    ExitLeft,
+    StayStraight,
+    StayRight,
+    StayLeft,
+    Merge,
+    RoundaboutEnter,
+    RoundaboutExit,
+    FerryEnter,
+    FerryExit,
+    Transit,
+    TransitTransfer,
+    TransitRemainOn,
+    TransitConnectionStart,
+    TransitConnectionTransfer,
+    TransitConnectionDestination,
+    /// Any maneuver type this crate doesn't have a named variant for (including "none", `0`).
+    Other(u32),
+}
+
+impl ManeuverKind {
+    fn from_type(value: u32) -> Self {
+        match value {
+            1 => Self::Start,
+            2 => Self::StartRight,
+            3 => Self::StartLeft,
+            4 => Self::Destination,
+            5 => Self::DestinationRight,
+            6 => Self::DestinationLeft,
+            7 => Self::Becomes,
+            8 => Self::Continue,
+            9 => Self::SlightRight,
+            10 => Self::Right,
+            11 => Self::SharpRight,
+            12 => Self::UturnRight,
+            13 => Self::UturnLeft,
+            14 => Self::SharpLeft,
+            15 => Self::Left,
+            16 => Self::SlightLeft,
+            17 => Self::RampStraight,
+            18 => Self::RampRight,
+            19 => Self::RampLeft,
+            20 => Self::ExitRight,
+            21 => Self::ExitLeft,
+            22 => Self::StayStraight,
+            23 => Self::StayRight,
+            24 => Self::StayLeft,
+            25 => Self::Merge,
+            26 => Self::RoundaboutEnter,
+            27 => Self::RoundaboutExit,
+            28 => Self::FerryEnter,
+            29 => Self::FerryExit,
+            30 => Self::Transit,
+            31 => Self::TransitTransfer,
+            32 => Self::TransitRemainOn,
+            33 => Self::TransitConnectionStart,
+            34 => Self::TransitConnectionTransfer,
+            35 => Self::TransitConnectionDestination,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// A single turn-by-turn instruction within a [`RouteLeg`].
+#[derive(Debug, Clone, Default)]
+pub struct Maneuver {
+    pub kind: ManeuverKind,
+    /// Human-readable instruction text, e.g. "Turn right onto Main St.". Empty if the source
+    /// response doesn't carry narration (some [`Response::Pbf`] trips don't).
+    pub instruction: String,
+    /// Street names this maneuver travels along. Only populated from [`Response::Json`]; the PBF
+    /// `TripLeg` doesn't expose them in a format this crate parses yet.
+    pub street_names: Vec<String>,
+    pub time_secs: f64,
+    pub begin_shape_index: usize,
+    pub end_shape_index: usize,
+}
+
+impl Default for ManeuverKind {
+    fn default() -> Self {
+        Self::Other(0)
+    }
+}
+
+/// One leg of a [`RouteResult`], matching Valhalla's own per-origin/destination-pair leg split.
+/// Not to be confused with [`crate::itinerary::Leg`], which further splits a leg by travel mode for
+/// multimodal trips.
+#[derive(Debug, Clone, Default)]
+pub struct RouteLeg {
+    pub maneuvers: Vec<Maneuver>,
+    pub shape: Vec<LatLon>,
+    pub length_m: f64,
+    pub time_secs: f64,
+}
+
+/// A parsed [`Actor::route`](crate::Actor::route)/[`Actor::optimized_route`](crate::Actor::optimized_route) response.
+#[derive(Debug, Clone, Default)]
+pub struct RouteResult {
+    pub legs: Vec<RouteLeg>,
+}
+
+/// One source/target cell of a [`MatrixResult`]. `None` fields mean Valhalla couldn't find a path
+/// for that pair.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimeDistance {
+    pub time_secs: Option<f64>,
+    pub distance_km: Option<f64>,
+}
+
+/// A parsed [`Actor::matrix`](crate::Actor::matrix) response: `cells[source_index][target_index]`.
+#[derive(Debug, Clone, Default)]
+pub struct MatrixResult {
+    pub cells: Vec<Vec<TimeDistance>>,
+}
+
+/// One contour of a parsed [`Actor::isochrone`](crate::Actor::isochrone) response. Isochrones are
+/// returned as GeoJSON `Polygon`/`MultiPolygon` features; [`Self::rings`] flattens both shapes into
+/// a list of linear rings (a `MultiPolygon`'s rings across all of its polygons, concatenated).
+#[derive(Debug, Clone, Default)]
+pub struct IsochroneContour {
+    /// The contour's time (minutes) or distance (km) value, per the original request.
+    pub contour_value: f64,
+    /// Fill color as requested, e.g. `"ff0000"`.
+    pub color: String,
+    pub rings: Vec<Vec<LatLon>>,
+}
+
+impl Response {
+    /// Parses a `route`/`optimized_route` response into a [`RouteResult`]. Supports
+    /// [`Response::Pbf`] and [`Response::Json`]; [`Response::Other`] (e.g. `osrm`, `gpx`) isn't
+    /// structured enough to parse generically and returns an error.
+    pub fn route_result(&self) -> Result<RouteResult, Error> {
+        match self {
+            Response::Pbf(api) => route_result_from_pbf(api),
+            Response::Json(json) => route_result_from_json(json),
+            Response::Other(_) => Err(Error("route_result needs a Pbf or Json response".into())),
+        }
+    }
+
+    /// Parses a `matrix` response into a [`MatrixResult`]. Only [`Response::Json`] is supported:
+    /// Valhalla's matrix PBF payload doesn't carry the per-cell results the trip PBF does.
+    pub fn matrix_result(&self) -> Result<MatrixResult, Error> {
+        let Response::Json(json) = self else {
+            return Err(Error("matrix_result needs a Json response".into()));
+        };
+        matrix_result_from_json(json)
+    }
+
+    /// Parses an `isochrone` response into its GeoJSON contour features. Valhalla's isochrone
+    /// endpoint always responds with GeoJSON, whatever format was requested, so both
+    /// [`Response::Json`] and the raw bytes of [`Response::Other`] are accepted.
+    pub fn isochrone_features(&self) -> Result<Vec<IsochroneContour>, Error> {
+        let json = match self {
+            Response::Json(json) => json.as_str(),
+            Response::Other(bytes) => std::str::from_utf8(bytes).map_err(|err| Error(err.to_string().into()))?,
+            Response::Pbf(_) => return Err(Error("isochrone_features needs a Json response".into())),
+        };
+        isochrone_features_from_json(json)
+    }
+}
+
+fn route_result_from_pbf(api: &crate::proto::Api) -> Result<RouteResult, Error> {
+    let trip = api.trip.as_ref().ok_or_else(|| Error("response has no trip".into()))?;
+
+    let legs = trip
+        .legs
+        .iter()
+        .map(|trip_leg| {
+            let shape = geometry::decode_polyline(&trip_leg.shape, 6);
+            let maneuvers: Vec<Maneuver> = trip_leg
+                .maneuvers
+                .iter()
+                .map(|maneuver| Maneuver {
+                    kind: ManeuverKind::from_type(maneuver.r#type),
+                    instruction: maneuver.instruction.clone(),
+                    street_names: Vec::new(),
+                    time_secs: maneuver.time,
+                    begin_shape_index: maneuver.begin_shape_index as usize,
+                    end_shape_index: maneuver.end_shape_index as usize,
+                })
+                .collect();
+            let time_secs = maneuvers.iter().map(|maneuver| maneuver.time_secs).sum();
+            let length_m = shape_length_m(&shape);
+            RouteLeg {
+                maneuvers,
+                shape,
+                length_m,
+                time_secs,
+            }
+        })
+        .collect();
+    Ok(RouteResult { legs })
+}
+
+fn route_result_from_json(json: &str) -> Result<RouteResult, Error> {
+    let value: serde_json::Value = serde_json::from_str(json).map_err(|err| Error(err.to_string().into()))?;
+    let legs = value
+        .get("trip")
+        .and_then(|trip| trip.get("legs"))
+        .and_then(|legs| legs.as_array())
+        .ok_or_else(|| Error("route response has no trip.legs".into()))?;
+
+    let legs = legs
+        .iter()
+        .map(|leg| {
+            let shape_str = leg.get("shape").and_then(|v| v.as_str()).unwrap_or_default();
+            let shape = geometry::decode_polyline(shape_str, 6);
+
+            let maneuvers: Vec<Maneuver> = leg
+                .get("maneuvers")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .map(|maneuver| Maneuver {
+                    kind: ManeuverKind::from_type(maneuver.get("type").and_then(|v| v.as_u64()).unwrap_or(0) as u32),
+                    instruction: maneuver.get("instruction").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                    street_names: maneuver
+                        .get("street_names")
+                        .and_then(|v| v.as_array())
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|name| name.as_str().map(str::to_string))
+                        .collect(),
+                    time_secs: maneuver.get("time").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                    begin_shape_index: maneuver.get("begin_shape_index").and_then(|v| v.as_u64()).unwrap_or(0) as usize,
+                    end_shape_index: maneuver.get("end_shape_index").and_then(|v| v.as_u64()).unwrap_or(0) as usize,
+                })
+                .collect();
+
+            let time_secs = maneuvers.iter().map(|maneuver| maneuver.time_secs).sum();
+            let length_m = shape_length_m(&shape);
+            RouteLeg {
+                maneuvers,
+                shape,
+                length_m,
+                time_secs,
+            }
+        })
+        .collect();
+    Ok(RouteResult { legs })
+}
+
+fn matrix_result_from_json(json: &str) -> Result<MatrixResult, Error> {
+    let value: serde_json::Value = serde_json::from_str(json).map_err(|err| Error(err.to_string().into()))?;
+    let rows = value
+        .get("sources_to_targets")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| Error("matrix response has no sources_to_targets".into()))?;
+
+    let cells = rows
+        .iter()
+        .map(|row| {
+            row.as_array()
+                .into_iter()
+                .flatten()
+                .map(|cell| TimeDistance {
+                    time_secs: cell.get("time").and_then(|v| v.as_f64()),
+                    distance_km: cell.get("distance").and_then(|v| v.as_f64()),
+                })
+                .collect()
+        })
+        .collect();
+    Ok(MatrixResult { cells })
+}
+
+fn isochrone_features_from_json(json: &str) -> Result<Vec<IsochroneContour>, Error> {
+    let value: serde_json::Value = serde_json::from_str(json).map_err(|err| Error(err.to_string().into()))?;
+    let features = value
+        .get("features")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| Error("isochrone response has no features".into()))?;
+
+    Ok(features
+        .iter()
+        .filter_map(|feature| {
+            let properties = feature.get("properties")?;
+            let contour_value = properties.get("contour").and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let color = properties.get("color").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+
+            let geometry = feature.get("geometry")?;
+            let geometry_type = geometry.get("type").and_then(|v| v.as_str()).unwrap_or_default();
+            let coordinates = geometry.get("coordinates")?;
+
+            let rings = match geometry_type {
+                "Polygon" => parse_rings(coordinates),
+                "MultiPolygon" => coordinates.as_array()?.iter().flat_map(parse_rings).collect(),
+                _ => Vec::new(),
+            };
+
+            Some(IsochroneContour {
+                contour_value,
+                color,
+                rings,
+            })
+        })
+        .collect())
+}
+
+/// Parses a GeoJSON `Polygon`'s `coordinates` (a list of linear rings, each a list of `[lon, lat]`
+/// pairs) into `LatLon` rings.
+fn parse_rings(polygon: &serde_json::Value) -> Vec<Vec<LatLon>> {
+    polygon
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|ring| {
+            ring.as_array().map(|points| {
+                points
+                    .iter()
+                    .filter_map(|point| {
+                        let coords = point.as_array()?;
+                        let lon = coords.first()?.as_f64()?;
+                        let lat = coords.get(1)?.as_f64()?;
+                        Some(LatLon(lat, lon))
+                    })
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+fn shape_length_m(shape: &[LatLon]) -> f64 {
+    shape.windows(2).map(|pair| geometry::haversine_distance_m(pair[0], pair[1])).sum()
+}
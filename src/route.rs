@@ -0,0 +1,205 @@
+//! Built-in A* shortest-path routing on top of [`CostingModel`], promoted from the cross-tile
+//! label-setting loop the `costing_model` test hand-rolled over `tile.directededges()[node.edges()]`.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::{CostingModel, GraphId, GraphReader, LatLon, SpeedSources, geometry};
+
+/// Highest speed any edge can have (Valhalla stores edge speeds in 8 bits), in km/h, used as the
+/// admissible lower bound for the A* heuristic (so `h` never overestimates the true remaining cost).
+const MAX_SPEED_KMH: f64 = 255.0;
+
+/// Result of [`GraphReader::route`].
+#[derive(Debug, Clone)]
+pub struct Route {
+    /// Directed edges traversed, in travel order, as their `GraphId`.
+    pub edges: Vec<GraphId>,
+    /// Total travel time, in seconds.
+    pub cost_seconds: f64,
+    /// Total length of the route, in meters.
+    pub length_m: u64,
+}
+
+/// Min-heap entry ordered by ascending `f = g + h`, so [`BinaryHeap`] (a max-heap) pops the lowest
+/// cost first via [`Reverse`](std::cmp::Reverse)-style inverted ordering.
+struct Frontier {
+    f: f64,
+    g: f64,
+    node: GraphId,
+}
+
+impl PartialEq for Frontier {
+    fn eq(&self, other: &Self) -> bool {
+        self.f == other.f
+    }
+}
+impl Eq for Frontier {}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so `BinaryHeap` (a max-heap) acts as a min-heap on `f`.
+        other.f.total_cmp(&self.f)
+    }
+}
+
+impl GraphReader {
+    /// Finds the shortest path from `origin` to `dest` (both node `GraphId`s) using A*, accessible
+    /// per `costing`. Returns `None` if no accessible path exists.
+    ///
+    /// `g` is accumulated travel time, computed from [`crate::GraphTile::edge_speed`] over each
+    /// edge's length; `h` is the haversine distance from the current node to `dest` divided by
+    /// [`MAX_SPEED_KMH`], an admissible (never-overestimating) lower bound. Tile boundaries are
+    /// handled the same way the `costing_model` test does: the tile is reloaded whenever
+    /// `node_id.tile()` changes.
+    pub fn route(&self, origin: GraphId, dest: GraphId, costing: &CostingModel) -> Option<Route> {
+        self.route_impl(origin, dest, costing, None)
+    }
+
+    /// Like [`Self::route`], but looks up edge speeds local to `departure_unix_timestamp` instead
+    /// of Valhalla's time-agnostic defaults, via [`crate::GraphTile::local_edge_speed`]. The
+    /// timestamp advances by each edge's travel time before the next edge's speed lookup, so a
+    /// route crossing into evening rush hour sees rush-hour speeds on the edges it actually
+    /// reaches during it.
+    pub fn route_departing_at(
+        &self,
+        origin: GraphId,
+        dest: GraphId,
+        costing: &CostingModel,
+        departure_unix_timestamp: u64,
+    ) -> Option<Route> {
+        self.route_impl(origin, dest, costing, Some(departure_unix_timestamp))
+    }
+
+    fn route_impl(
+        &self,
+        origin: GraphId,
+        dest: GraphId,
+        costing: &CostingModel,
+        departure_unix_timestamp: Option<u64>,
+    ) -> Option<Route> {
+        let dest_tile = self.graph_tile(dest.tile())?;
+        let dest_node = dest_tile.node(dest.id())?;
+        let dest_latlon = dest_tile.node_latlon(dest_node);
+
+        let mut best_cost: HashMap<GraphId, f64> = HashMap::new();
+        let mut predecessor: HashMap<GraphId, (GraphId, GraphId)> = HashMap::new(); // node -> (prev_node, edge)
+        let mut open = BinaryHeap::new();
+
+        best_cost.insert(origin, 0.0);
+        open.push(Frontier {
+            f: 0.0,
+            g: 0.0,
+            node: origin,
+        });
+
+        let mut tile_id = origin.tile();
+        let mut tile = self.graph_tile(tile_id)?;
+
+        while let Some(Frontier { g, node, .. }) = open.pop() {
+            if node == dest {
+                return Some(self.reconstruct_route(origin, dest, g, &predecessor));
+            }
+            if g > *best_cost.get(&node).unwrap_or(&f64::INFINITY) {
+                continue; // stale heap entry
+            }
+
+            if node.tile() != tile_id {
+                tile_id = node.tile();
+                tile = self.graph_tile(tile_id)?;
+            }
+            let Some(node_info) = tile.node(node.id()) else {
+                continue;
+            };
+            if !costing.node_accessible(node_info) {
+                continue;
+            }
+
+            let begin_index = node_info.edge_index();
+            for (offset, edge) in tile.node_edges(node_info).iter().enumerate() {
+                if !costing.edge_accessible(edge) {
+                    continue;
+                }
+
+                let speed_kmh = match departure_unix_timestamp {
+                    Some(departure) => {
+                        let (speed_kmh, _) =
+                            tile.local_edge_speed(edge, node_info, departure + g.round() as u64, SpeedSources::ALL, false);
+                        speed_kmh
+                    }
+                    None => tile.edge_speed(edge, SpeedSources::ALL, false, 0, 0).0,
+                };
+                let travel_time_s = edge.length() as f64 / (speed_kmh.max(1) as f64 * 1000.0 / 3600.0);
+                let next_node = edge.endnode();
+                let next_g = g + travel_time_s;
+
+                if next_g < *best_cost.get(&next_node).unwrap_or(&f64::INFINITY) {
+                    best_cost.insert(next_node, next_g);
+                    let edge_index = begin_index + offset as u32;
+                    let Some(edge_id) = GraphId::from_parts(tile_id.level(), tile_id.tileid(), edge_index) else {
+                        continue;
+                    };
+                    predecessor.insert(next_node, (node, edge_id));
+
+                    let h = heuristic(self, next_node, dest_latlon);
+                    open.push(Frontier {
+                        f: next_g + h,
+                        g: next_g,
+                        node: next_node,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn reconstruct_route(
+        &self,
+        origin: GraphId,
+        dest: GraphId,
+        cost_seconds: f64,
+        predecessor: &HashMap<GraphId, (GraphId, GraphId)>,
+    ) -> Route {
+        let mut edges = Vec::new();
+        let mut length_m = 0u64;
+        let mut node = dest;
+        while node != origin {
+            let Some(&(prev_node, edge_id)) = predecessor.get(&node) else {
+                break;
+            };
+            edges.push(edge_id);
+            if let Some(tile) = self.graph_tile(edge_id.tile()) {
+                if let Some(edge) = tile.directededges().get(edge_id.id() as usize) {
+                    length_m += edge.length() as u64;
+                }
+            }
+            node = prev_node;
+        }
+        edges.reverse();
+        Route {
+            edges,
+            cost_seconds,
+            length_m,
+        }
+    }
+}
+
+/// Admissible A* heuristic: haversine distance from `node` to `dest_latlon`, divided by the
+/// fastest speed any edge could plausibly have, so it never overestimates the true remaining cost.
+fn heuristic(reader: &GraphReader, node: GraphId, dest_latlon: LatLon) -> f64 {
+    let Some(tile) = reader.graph_tile(node.tile()) else {
+        return 0.0;
+    };
+    let Some(node_info) = tile.node(node.id()) else {
+        return 0.0;
+    };
+    let node_latlon = tile.node_latlon(node_info);
+    let distance_m = geometry::haversine_distance_m(node_latlon, dest_latlon);
+    distance_m / (MAX_SPEED_KMH * 1000.0 / 3600.0)
+}
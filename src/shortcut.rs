@@ -0,0 +1,130 @@
+//! Shortcut edge recovery: expanding a contracted `is_shortcut()` edge back into the sequence of
+//! base-level edges it supersedes, needed for attribute extraction and map-matching over the
+//! hierarchy levels shortcuts are built on top of.
+
+use crate::{GraphId, GraphReader, GraphTile};
+
+/// Tolerance, in meters, allowed between a shortcut's stored length and the summed length of the
+/// base edges recovered for it, to absorb rounding in how Valhalla packs edge lengths.
+const LENGTH_TOLERANCE_M: u32 = 5;
+
+impl GraphReader {
+    /// Expands `shortcut` (a directed edge `GraphId` with `is_shortcut() == true`) back into the
+    /// ordered base-level directed edges it supersedes. Returns an empty `Vec` if `shortcut` isn't
+    /// actually a shortcut, or if recovery diverges (no superseded continuation at some node) or
+    /// overshoots the shortcut's stored length.
+    ///
+    /// Starts at the shortcut's begin node, drops down to the next (less contracted) hierarchy
+    /// level via a downward [`crate::NodeTransition`] if one exists, then greedily walks the
+    /// first `superseded()` outbound edge at each node (any other superseded edge at the same node
+    /// is not considered), accumulating length, until the running
+    /// length reaches the shortcut's length and the current node is the shortcut's `endnode()`.
+    pub fn recover_shortcut(&self, shortcut: GraphId) -> Vec<GraphId> {
+        let Some(shortcut_tile) = self.graph_tile(shortcut.tile()) else {
+            return Vec::new();
+        };
+        let Some(shortcut_edge) = shortcut_tile.directededge(shortcut.id()) else {
+            return Vec::new();
+        };
+        if !shortcut_edge.is_shortcut() {
+            return Vec::new();
+        }
+        let target_length = shortcut_edge.length();
+
+        let Some(begin_index) = node_owning_edge(&shortcut_tile, shortcut.id()) else {
+            return Vec::new();
+        };
+        let Some(begin_node) = shortcut_tile.node(begin_index) else {
+            return Vec::new();
+        };
+
+        // Drop down to the next, less-contracted hierarchy level if the shortcut isn't already on
+        // the base level (no downward transition means it already is).
+        let mut current = shortcut_tile
+            .node_transitions(begin_node)
+            .iter()
+            .find(|transition| !transition.upward())
+            .map(|transition| transition.endnode())
+            .unwrap_or_else(|| GraphId::from_parts(shortcut.level(), shortcut.tileid(), begin_index).unwrap());
+
+        // The shortcut's own `endnode()` stays at the shortcut's (contracted) hierarchy level, but
+        // `current` was just dropped to the base level above, so the two would never compare equal
+        // as `GraphId`s (level is part of the packed id). Resolve the base-level node the shortcut's
+        // endnode corresponds to via its own downward transition before comparing.
+        let shortcut_end = shortcut_edge.endnode();
+        let target_end = self
+            .graph_tile(shortcut_end.tile())
+            .and_then(|end_tile| {
+                let end_node = end_tile.node(shortcut_end.id())?;
+                end_tile
+                    .node_transitions(end_node)
+                    .iter()
+                    .find(|transition| !transition.upward())
+                    .map(|transition| transition.endnode())
+            })
+            .unwrap_or(shortcut_end);
+
+        let mut edges = Vec::new();
+        let mut accumulated_length = 0u32;
+        let mut tile_id = current.tile();
+        let Some(mut tile) = self.graph_tile(tile_id) else {
+            return Vec::new();
+        };
+
+        loop {
+            if !edges.is_empty() && current == target_end {
+                break;
+            }
+
+            if current.tile() != tile_id {
+                tile_id = current.tile();
+                let Some(next_tile) = self.graph_tile(tile_id) else {
+                    return Vec::new();
+                };
+                tile = next_tile;
+            }
+            let Some(node_info) = tile.node(current.id()) else {
+                return Vec::new();
+            };
+
+            let Some((offset, edge)) = tile
+                .node_edges(node_info)
+                .iter()
+                .enumerate()
+                .find(|(_, edge)| edge.superseded())
+            else {
+                return Vec::new(); // diverged: no continuation at this node
+            };
+            let edge_index = node_info.edge_index() + offset as u32;
+            let Some(edge_id) = GraphId::from_parts(tile_id.level(), tile_id.tileid(), edge_index) else {
+                return Vec::new();
+            };
+
+            edges.push(edge_id);
+            accumulated_length += edge.length();
+            if accumulated_length > target_length + LENGTH_TOLERANCE_M {
+                return Vec::new(); // overshot
+            }
+            current = edge.endnode();
+        }
+
+        if accumulated_length + LENGTH_TOLERANCE_M < target_length {
+            return Vec::new(); // stopped short of the shortcut's full length
+        }
+        edges
+    }
+}
+
+/// Finds the index of the node whose outbound edge range (via [`GraphTile::node_edges()`] offset
+/// from [`crate::NodeInfo::edge_index()`]) contains `edge_index`, since a `DirectedEdge` doesn't
+/// store a back-reference to its begin node.
+pub(crate) fn node_owning_edge(tile: &GraphTile, edge_index: u32) -> Option<u32> {
+    tile.nodes()
+        .iter()
+        .position(|node| {
+            let start = node.edge_index();
+            let end = start + tile.node_edges(node).len() as u32;
+            (start..end).contains(&edge_index)
+        })
+        .map(|index| index as u32)
+}
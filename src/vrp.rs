@@ -0,0 +1,327 @@
+//! A depot-and-stops capacitated vehicle routing solver, layered on [`Actor::matrix`]: the
+//! Clarke-Wright savings algorithm builds an initial set of routes, then each route gets a 2-opt
+//! cleanup pass.
+
+use std::collections::HashMap;
+
+use crate::{Actor, Error, LatLon, actor::Response, proto};
+
+/// A stop a vehicle must visit, with its demand against the fleet's per-vehicle capacity.
+#[derive(Debug, Clone, Copy)]
+pub struct Stop {
+    pub location: LatLon,
+    pub demand: u32,
+}
+
+/// One vehicle's assignment: the stops it visits, in visiting order (as indices into the `stops`
+/// slice passed to [`solve`]), implicitly starting and ending at the depot.
+#[derive(Debug, Clone, Default)]
+pub struct VehicleRoute {
+    /// Stop indices, in visiting order.
+    pub stops: Vec<usize>,
+    /// Total demand carried by this route.
+    pub demand: u32,
+}
+
+/// Result of [`solve`].
+#[derive(Debug, Clone, Default)]
+pub struct Solution {
+    /// One entry per vehicle actually used; at most `vehicle_count` entries.
+    pub routes: Vec<VehicleRoute>,
+    /// Stop indices that couldn't be assigned: unreachable from the depot per [`Actor::matrix`],
+    /// too much demand for any single vehicle's capacity, or left over once every vehicle's route
+    /// was full.
+    pub unassigned: Vec<usize>,
+}
+
+/// Solves a capacitated vehicle routing problem for `stops` from `depot`, across up to
+/// `vehicle_count` vehicles each with `vehicle_capacity` demand.
+///
+/// Distances come from a single [`Actor::matrix`] call over `depot` + `stops` (depot first, so
+/// location index `i + 1` is `stops[i]`). The Clarke-Wright savings heuristic then greedily merges
+/// the pair of single-stop routes with the largest savings `s(i,j) = d(depot,i) + d(depot,j) -
+/// d(i,j)` — using the symmetrized `(d(i,j) + d(j,i)) / 2` since savings assumes undirected
+/// distances — repeating until no merge both respects `vehicle_capacity` and reduces the route
+/// count. Each resulting route then gets a 2-opt cleanup pass scored on the true directed
+/// distances, since a road network's forward and return costs can differ. A stop [`Actor::matrix`]
+/// can't reach from the depot, or that doesn't fit in any vehicle's capacity or in the
+/// `vehicle_count` budget, ends up in [`Solution::unassigned`] rather than panicking.
+pub fn solve(
+    actor: &mut Actor,
+    costing_type: proto::costing::Type,
+    depot: LatLon,
+    stops: &[Stop],
+    vehicle_count: usize,
+    vehicle_capacity: u32,
+) -> Result<Solution, Error> {
+    let locations: Vec<LatLon> = std::iter::once(depot).chain(stops.iter().map(|stop| stop.location)).collect();
+    let matrix = fetch_matrix(actor, costing_type, &locations)?;
+    Ok(clarke_wright(&matrix, stops, vehicle_count, vehicle_capacity))
+}
+
+/// `size x size` matrix of directed distances between `depot` (index `0`) and `stops` (indices
+/// `1..=stops.len()`), in whatever unit [`Actor::matrix`]'s JSON response reports (meters). A cell
+/// is `None` when the pair is unreachable.
+struct CostMatrix {
+    size: usize,
+    distance: Vec<Option<f64>>,
+}
+
+impl CostMatrix {
+    fn get(&self, from: usize, to: usize) -> Option<f64> {
+        self.distance[from * self.size + to]
+    }
+
+    /// Savings-algorithm symmetrization: the average of the two travel directions.
+    fn symmetric(&self, a: usize, b: usize) -> Option<f64> {
+        Some((self.get(a, b)? + self.get(b, a)?) / 2.0)
+    }
+}
+
+/// Runs a single [`Actor::matrix`] call over `locations` and parses its JSON
+/// `sources_to_targets` response into a [`CostMatrix`].
+fn fetch_matrix(actor: &mut Actor, costing_type: proto::costing::Type, locations: &[LatLon]) -> Result<CostMatrix, Error> {
+    let proto_locations: Vec<proto::Location> = locations
+        .iter()
+        .map(|&location| proto::Location {
+            ll: location.into(),
+            ..Default::default()
+        })
+        .collect();
+    let request = proto::Options {
+        format: proto::options::Format::Json as i32,
+        costing_type: costing_type as i32,
+        sources: proto_locations.clone(),
+        targets: proto_locations,
+        ..Default::default()
+    };
+
+    let Response::Json(json) = actor.matrix(&request)? else {
+        return Err(Error("Actor::matrix did not return a JSON response".into()));
+    };
+    let value: serde_json::Value = serde_json::from_str(&json).map_err(|err| Error(err.to_string().into()))?;
+    let rows = value
+        .get("sources_to_targets")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| Error("matrix response missing sources_to_targets".into()))?;
+
+    let size = locations.len();
+    let mut distance = vec![None; size * size];
+    for row in rows {
+        let Some(row) = row.as_array() else { continue };
+        for cell in row {
+            let (Some(from), Some(to)) = (
+                cell.get("from_index").and_then(|v| v.as_u64()).map(|v| v as usize),
+                cell.get("to_index").and_then(|v| v.as_u64()).map(|v| v as usize),
+            ) else {
+                continue;
+            };
+            if from >= size || to >= size {
+                continue; // out-of-range index in an untrusted response: skip rather than panic
+            }
+            if let Some(distance_m) = cell.get("distance").and_then(|v| v.as_f64()) {
+                distance[from * size + to] = Some(distance_m * 1000.0);
+            }
+        }
+    }
+    Ok(CostMatrix { size, distance })
+}
+
+/// `s(i, j) = d(depot, i) + d(depot, j) - d(i, j)`, using symmetrized distances. `i` and `j` are
+/// stop indices (`0`-based); location indices in `matrix` are offset by one for the depot.
+fn savings(matrix: &CostMatrix, i: usize, j: usize) -> Option<f64> {
+    let d_depot_i = matrix.symmetric(0, i + 1)?;
+    let d_depot_j = matrix.symmetric(0, j + 1)?;
+    let d_ij = matrix.symmetric(i + 1, j + 1)?;
+    Some(d_depot_i + d_depot_j - d_ij)
+}
+
+fn clarke_wright(matrix: &CostMatrix, stops: &[Stop], vehicle_count: usize, vehicle_capacity: u32) -> Solution {
+    let mut unassigned = Vec::new();
+
+    // A route per reachable, individually-servable stop; everything else is unassigned up front.
+    let mut routes: Vec<Option<Vec<usize>>> = Vec::new();
+    let mut demand: Vec<u32> = Vec::new();
+    let mut route_of: HashMap<usize, usize> = HashMap::new();
+    for (i, stop) in stops.iter().enumerate() {
+        let reachable = matrix.get(0, i + 1).is_some() && matrix.get(i + 1, 0).is_some();
+        if !reachable || stop.demand > vehicle_capacity {
+            unassigned.push(i);
+            continue;
+        }
+        route_of.insert(i, routes.len());
+        routes.push(Some(vec![i]));
+        demand.push(stop.demand);
+    }
+
+    let mut savings_list: Vec<(f64, usize, usize)> = Vec::new();
+    for &i in route_of.keys() {
+        for &j in route_of.keys() {
+            if i < j {
+                if let Some(s) = savings(matrix, i, j) {
+                    savings_list.push((s, i, j));
+                }
+            }
+        }
+    }
+    savings_list.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    for (_, i, j) in savings_list {
+        let (Some(&ri), Some(&rj)) = (route_of.get(&i), route_of.get(&j)) else {
+            continue;
+        };
+        if ri == rj {
+            continue;
+        }
+        if demand[ri] + demand[rj] > vehicle_capacity {
+            continue;
+        }
+        let (Some(route_i), Some(route_j)) = (routes[ri].as_ref(), routes[rj].as_ref()) else {
+            continue;
+        };
+
+        // Only `i` and `j` at the ends of their routes can be joined without splicing a stop out of
+        // the middle of a route. Whichever end each sits at, reverse one sub-route so `i` and `j`
+        // end up adjacent in the middle of the merged route.
+        let merged = if *route_i.last().unwrap() == i && *route_j.first().unwrap() == j {
+            [route_i.as_slice(), route_j.as_slice()].concat()
+        } else if *route_j.last().unwrap() == j && *route_i.first().unwrap() == i {
+            [route_j.as_slice(), route_i.as_slice()].concat()
+        } else if *route_i.last().unwrap() == i && *route_j.last().unwrap() == j {
+            let reversed_j: Vec<usize> = route_j.iter().rev().copied().collect();
+            [route_i.as_slice(), reversed_j.as_slice()].concat()
+        } else if *route_i.first().unwrap() == i && *route_j.first().unwrap() == j {
+            let reversed_i: Vec<usize> = route_i.iter().rev().copied().collect();
+            [reversed_i.as_slice(), route_j.as_slice()].concat()
+        } else {
+            continue;
+        };
+
+        for &stop in &merged {
+            route_of.insert(stop, ri);
+        }
+        demand[ri] += demand[rj];
+        routes[ri] = Some(merged);
+        routes[rj] = None;
+    }
+
+    let mut final_routes: Vec<Vec<usize>> = routes.into_iter().flatten().collect();
+    if final_routes.len() > vehicle_count {
+        // Keep the largest routes (fewest stops left to redistribute) and give up on the rest:
+        // Clarke-Wright alone can't always hit an exact fleet size while respecting capacity.
+        final_routes.sort_by_key(|route| std::cmp::Reverse(route.len()));
+        for leftover in final_routes.split_off(vehicle_count) {
+            unassigned.extend(leftover);
+        }
+    }
+
+    let routes = final_routes
+        .into_iter()
+        .map(|route| {
+            let route = two_opt(matrix, route);
+            let demand = route.iter().map(|&i| stops[i].demand).sum();
+            VehicleRoute { stops: route, demand }
+        })
+        .collect();
+
+    unassigned.sort_unstable();
+    Solution { routes, unassigned }
+}
+
+/// Total directed travel distance of `depot -> route[0] -> ... -> route[-1] -> depot`.
+fn route_distance(matrix: &CostMatrix, route: &[usize]) -> f64 {
+    let mut total = 0.0;
+    let mut prev = 0; // depot
+    for &stop in route {
+        total += matrix.get(prev, stop + 1).unwrap_or(f64::INFINITY);
+        prev = stop + 1;
+    }
+    total + matrix.get(prev, 0).unwrap_or(f64::INFINITY)
+}
+
+/// Local-search cleanup: repeatedly reverses the best-improving `route[i..=j]` segment until no
+/// reversal improves [`route_distance`]. Re-scores the whole route per candidate (rather than just
+/// the two changed edges) since every edge inside a reversed segment also flips travel direction,
+/// and a road network's forward/return costs aren't assumed equal.
+fn two_opt(matrix: &CostMatrix, mut route: Vec<usize>) -> Vec<usize> {
+    if route.len() < 3 {
+        return route;
+    }
+
+    let mut best_distance = route_distance(matrix, &route);
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 0..route.len() - 1 {
+            for j in (i + 1)..route.len() {
+                let mut candidate = route.clone();
+                candidate[i..=j].reverse();
+                let candidate_distance = route_distance(matrix, &candidate);
+                if candidate_distance < best_distance {
+                    route = candidate;
+                    best_distance = candidate_distance;
+                    improved = true;
+                }
+            }
+        }
+    }
+    route
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a `CostMatrix` from explicit pairwise distances, mirroring them across both travel
+    /// directions; unlisted pairs stay unreachable (`None`).
+    fn symmetric_matrix(size: usize, pairs: &[((usize, usize), f64)]) -> CostMatrix {
+        let mut distance = vec![None; size * size];
+        for &((a, b), d) in pairs {
+            distance[a * size + b] = Some(d);
+            distance[b * size + a] = Some(d);
+        }
+        CostMatrix { size, distance }
+    }
+
+    #[test]
+    fn clarke_wright_merges_same_side_routes_via_reversal() {
+        // depot = location 0; stops A, B, C, D = locations 1..=4 (stop indices 0..=3).
+        // A-B and C-D have the highest savings and merge first into the two-stop routes [A,B] and
+        // [C,D]. The next-highest savings pair is B-D, but B is the *tail* of the first route and D
+        // is the *tail* of the second, so merging them requires reversing one route rather than
+        // simply concatenating — exactly the case that used to be dropped on the floor.
+        let matrix = symmetric_matrix(
+            5,
+            &[
+                ((0, 1), 10.0), // depot-A
+                ((0, 2), 10.0), // depot-B
+                ((0, 3), 10.0), // depot-C
+                ((0, 4), 10.0), // depot-D
+                ((1, 2), 1.0),  // A-B
+                ((3, 4), 1.0),  // C-D
+                ((2, 4), 2.0),  // B-D: same-side merge, needs a reversal
+                ((1, 3), 18.0), // A-C: low savings, shouldn't interfere
+                ((1, 4), 18.0), // A-D
+                ((2, 3), 18.0), // B-C
+            ],
+        );
+        let stops = vec![
+            Stop {
+                location: LatLon(0.0, 0.0),
+                demand: 1,
+            };
+            4
+        ];
+
+        let solution = clarke_wright(&matrix, &stops, 1, 4);
+
+        assert!(
+            solution.unassigned.is_empty(),
+            "the B-D merge needs a route reversal, otherwise one whole route is dropped: {solution:?}"
+        );
+        assert_eq!(solution.routes.len(), 1);
+        let mut visited = solution.routes[0].stops.clone();
+        visited.sort_unstable();
+        assert_eq!(visited, vec![0, 1, 2, 3]);
+    }
+}
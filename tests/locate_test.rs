@@ -0,0 +1,53 @@
+use valhalla::{Config, CostingModel, GraphId, GraphReader, proto};
+
+const ANDORRA_TILES: &str = "tests/andorra/tiles.tar";
+
+/// Regression test for the "opposite edge" half of `GraphReader::locate()`: for a located edge
+/// whose end node's outbound edges don't start at tile-global index 0, the opposing edge must be
+/// resolved via `end_node.edge_index() + de.opp_index()`, not `de.opp_index()` alone.
+#[test]
+fn locate_returns_both_directions() {
+    let reader = GraphReader::new(&Config::from_tile_extract(ANDORRA_TILES).unwrap())
+        .expect("Failed to create GraphReader");
+    let costing = CostingModel::new(proto::costing::Type::Auto).unwrap();
+
+    let mut checked_with_nonzero_offset = false;
+    for tile_id in reader.tiles() {
+        let tile = reader.graph_tile(tile_id).unwrap();
+        for (edge_index, de) in tile.directededges().iter().enumerate() {
+            if de.leaves_tile() || de.is_shortcut() {
+                continue;
+            }
+            let end_node = tile.node(de.endnode().id()).unwrap();
+            // Only a node whose own outbound edges start partway into the tile's edge array can
+            // tell a correct `edge_index() + opp_index()` computation apart from a buggy one that
+            // uses `opp_index()` alone.
+            if end_node.edge_index() == 0 {
+                continue;
+            }
+
+            let shape = tile.edgeinfo(de).decoded_shape();
+            let midpoint = shape[shape.len() / 2];
+            let results = reader.locate(midpoint, 10.0, &costing);
+
+            let forward_id = GraphId::from_parts(tile_id.level(), tile_id.tileid(), edge_index as u32).unwrap();
+            let expected_opposite_index = end_node.edge_index() + de.opp_index();
+            let expected_opposite_id =
+                GraphId::from_parts(tile_id.level(), tile_id.tileid(), expected_opposite_index).unwrap();
+
+            assert!(
+                results.iter().any(|r| r.edge_id == forward_id),
+                "locate() should find the edge itself near its own shape midpoint"
+            );
+            assert!(
+                results.iter().any(|r| r.edge_id == expected_opposite_id),
+                "locate() should also find the correctly-offset opposing edge"
+            );
+            checked_with_nonzero_offset = true;
+        }
+    }
+    assert!(
+        checked_with_nonzero_offset,
+        "this tileset should contain at least one node with edge_index() != 0"
+    );
+}
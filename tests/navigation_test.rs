@@ -0,0 +1,105 @@
+use valhalla::{
+    Actor, Config, LatLon, NavigationSession, NavigationState, Response, resample_shape,
+    proto::{self, options::Format},
+};
+
+const ANDORRA_CONFIG: &str = "tests/andorra/config.json";
+const ANDORRA_TEST_LOC_1: LatLon = LatLon(42.50107335756198, 1.510341967860551); // Sant Julia de Loria
+const ANDORRA_TEST_LOC_2: LatLon = LatLon(42.50627089323736, 1.521734167223563); // Andorra la Vella
+
+fn route_response(actor: &mut Actor) -> Response {
+    let request = proto::Options {
+        format: Format::Pbf as i32,
+        costing_type: proto::costing::Type::Auto as i32,
+        locations: vec![
+            proto::Location {
+                ll: ANDORRA_TEST_LOC_1.into(),
+                ..Default::default()
+            },
+            proto::Location {
+                ll: ANDORRA_TEST_LOC_2.into(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    actor.route(&request).expect("route request should succeed")
+}
+
+#[test]
+fn fix_on_route_does_not_reroute() {
+    let config = Config::from_file(ANDORRA_CONFIG).unwrap();
+    let mut actor = Actor::new(&config).unwrap();
+    let response = route_response(&mut actor);
+
+    let mut session = NavigationSession::new(&response, ANDORRA_TEST_LOC_2, 25.0).unwrap();
+    match session.update(&mut actor, ANDORRA_TEST_LOC_1) {
+        NavigationState::OnRoute { off_route, .. } => assert!(!off_route, "fix at the route's own origin should be on-route"),
+        NavigationState::Rerouted(_) => panic!("should not reroute on the first on-route fix"),
+    }
+}
+
+#[test]
+fn persistent_off_route_fixes_trigger_reroute() {
+    let config = Config::from_file(ANDORRA_CONFIG).unwrap();
+    let mut actor = Actor::new(&config).unwrap();
+    let response = route_response(&mut actor);
+
+    // Far enough from the Andorra tileset's route geometry to never snap within threshold.
+    let far_away = LatLon(42.6, 1.4);
+    let mut session = NavigationSession::new(&response, ANDORRA_TEST_LOC_2, 25.0).unwrap();
+
+    let mut rerouted = false;
+    for _ in 0..5 {
+        match session.update(&mut actor, far_away) {
+            NavigationState::OnRoute { off_route, .. } => assert!(off_route, "fix far from the route should be flagged off-route"),
+            NavigationState::Rerouted(_) => {
+                rerouted = true;
+                break;
+            }
+        }
+    }
+    assert!(rerouted, "persistent off-route fixes should eventually trigger a reroute");
+}
+
+#[test]
+fn traveled_distance_does_not_snap_backward() {
+    let config = Config::from_file(ANDORRA_CONFIG).unwrap();
+    let mut actor = Actor::new(&config).unwrap();
+    let response = route_response(&mut actor);
+
+    // Baseline: remaining distance when the traveled-distance cursor hasn't advanced at all.
+    let mut fresh_session = NavigationSession::new(&response, ANDORRA_TEST_LOC_2, 25.0).unwrap();
+    let baseline_remaining = match fresh_session.update(&mut actor, ANDORRA_TEST_LOC_1) {
+        NavigationState::OnRoute { remaining_distance, .. } => remaining_distance,
+        NavigationState::Rerouted(_) => panic!("should not reroute on the first on-route fix"),
+    };
+
+    // Advance the cursor to near the destination, then feed a fix back at the route's own origin.
+    // Without a traveled-distance cursor this would snap back to the start of the route and report
+    // ~baseline_remaining again; with the cursor, the closest point considered is restricted to
+    // what's ahead of where the traveler already reached.
+    let mut session = NavigationSession::new(&response, ANDORRA_TEST_LOC_2, 25.0).unwrap();
+    session.update(&mut actor, ANDORRA_TEST_LOC_2);
+    let regressive_remaining = match session.update(&mut actor, ANDORRA_TEST_LOC_1) {
+        NavigationState::OnRoute { remaining_distance, .. } => remaining_distance,
+        NavigationState::Rerouted(_) => panic!("should not reroute on the second update"),
+    };
+
+    assert!(
+        regressive_remaining < baseline_remaining / 2.0,
+        "a fix back at the route's origin shouldn't re-report close to the full route's remaining \
+         distance once the traveler already progressed toward the destination \
+         (baseline={baseline_remaining}, regressive={regressive_remaining})"
+    );
+}
+
+#[test]
+fn resample_shape_keeps_endpoints_and_adds_points() {
+    let shape = vec![ANDORRA_TEST_LOC_1, ANDORRA_TEST_LOC_2];
+    let resampled = resample_shape(&shape, 100.0);
+
+    assert_eq!(resampled.first(), shape.first());
+    assert_eq!(resampled.last(), shape.last());
+    assert!(resampled.len() > 2, "a long segment should get intermediate points");
+}
@@ -476,3 +476,47 @@ fn wrong_tile_node_transitions() {
     let node = t2.node(0).unwrap();
     let _ = t1.node_transitions(node); // should panic
 }
+
+#[test]
+fn recover_shortcut() {
+    let config = ValhallaConfig {
+        mjolnir: MjolnirConfig {
+            tile_extract: ANDORRA_TILES.into(),
+            traffic_extract: ANDORRA_TRAFFIC.into(),
+        },
+    };
+    let reader = GraphReader::new(&Config::from_json(&json::to_string(&config)).unwrap())
+        .expect("Failed to create GraphReader");
+
+    let mut recovered_any = false;
+    for tile_id in reader.tiles() {
+        let tile = reader.graph_tile(tile_id).unwrap();
+        for (index, de) in tile.directededges().iter().enumerate() {
+            if !de.is_shortcut() {
+                continue;
+            }
+            let shortcut_id = GraphId::from_parts(tile_id.level(), tile_id.tileid(), index as u32).unwrap();
+            let base_edges = reader.recover_shortcut(shortcut_id);
+            assert!(
+                !base_edges.is_empty(),
+                "every shortcut in this tileset should recover to at least one base edge"
+            );
+
+            // Base edges should sum up (within tolerance) to the shortcut's own length.
+            let recovered_length: u32 = base_edges
+                .iter()
+                .map(|edge_id| {
+                    let edge_tile = reader.graph_tile(edge_id.tile()).unwrap();
+                    edge_tile.directededge(edge_id.id()).unwrap().length()
+                })
+                .sum();
+            assert!(
+                recovered_length.abs_diff(de.length()) <= 5,
+                "recovered length {recovered_length} should be close to shortcut length {}",
+                de.length()
+            );
+            recovered_any = true;
+        }
+    }
+    assert!(recovered_any, "this tileset should contain at least one shortcut edge");
+}